@@ -1,124 +1,412 @@
+use std::collections::HashMap;
+
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::api::{Label, Project, RequestFailed, Task, TaskCreate, TaskFilter};
-use std::error::Error;
+use crate::api::{
+    Comment, CommentCreate, CommentTarget, CompletedItem, Error, Item, Label, LabelCreate,
+    LabelUpdate, Project, ProjectCreate, ProjectUpdate, Result, RetryPolicy, Section,
+    SectionCreate, Task, TaskCreate, TaskFilter, TaskUpdate,
+};
 use std::ops::Add;
 
+/// Base URL of the Todoist REST v2 API.
+pub const DEFAULT_BASE_URL: &str = "https://api.todoist.com/rest/v2";
+
+/// Base URL of the Todoist Sync v9 API, which hosts the endpoints REST v2 lacks.
+pub const DEFAULT_SYNC_BASE_URL: &str = "https://api.todoist.com/sync/v9";
+
 pub struct Client {
     pub http_client: reqwest::Client,
+    base_url: String,
+    sync_base_url: String,
     bearer_token: (String, String),
+    retry: RetryPolicy,
 }
 
 impl Client {
-    pub fn new(client: reqwest::Client, token: String) -> Client {
-        return Client {
+    pub fn new(client: reqwest::Client, token: String, base_url: String) -> Client {
+        Client {
             http_client: client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            sync_base_url: DEFAULT_SYNC_BASE_URL.to_string(),
             bearer_token: (
                 String::from("Authorization"),
                 String::from("Bearer ").add(&token),
             ),
-        };
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Client {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_sync_base_url(mut self, sync_base_url: String) -> Client {
+        self.sync_base_url = sync_base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
     }
 
-    pub async fn find(&self, filter: Option<TaskFilter>) -> Result<Vec<Task>, Box<dyn Error>> {
+    pub async fn find(&self, filter: Option<TaskFilter>) -> Result<Vec<Task>> {
         let mut path: String = "/tasks".to_string();
         if let Some(f) = filter {
-            path = path.add(&f.to_string());
+            path = path.add(&f.to_query());
         }
 
-        return self.get::<Vec<Task>>(path).await;
-    }
-
-    pub async fn create(&self, task: TaskCreate) -> Result<Task, Box<dyn Error>> {
-        return self
-            .post::<TaskCreate, Task>(Some(task), String::from("/tasks"))
-            .await;
-    }
-
-    pub async fn close(&self, id: &String) -> Result<(), Box<dyn Error>> {
-        let path = "https://api.todoist.com/rest/v2/tasks/"
-            .to_string()
-            .add(&id)
-            .add("/close");
-
-        let resp = self
-            .http_client
-            .post(path)
-            .header(
-                self.bearer_token.0.to_owned(),
-                self.bearer_token.1.to_owned(),
-            )
-            .send()
-            .await;
-
-        match resp {
-            Ok(r) => {
-                if !r.status().is_success() {
-                    return Err(Box::new(RequestFailed::new(r.status().to_string())));
-                }
-                Ok(())
-            }
-            Err(e) => Err(Box::new(e)),
+        self.get::<Vec<Task>>(path).await
+    }
+
+    pub async fn create(&self, task: TaskCreate) -> Result<Task> {
+        self.post::<TaskCreate, Task>(Some(task), String::from("/tasks"))
+            .await
+    }
+
+    /// Creates a task from natural language, Todoist picks the project,
+    /// labels, priority and due date out of `text` like the apps do.
+    pub async fn quick_add(&self, text: &str) -> Result<Item> {
+        let url = self.sync_base_url.to_owned().add("/quick/add");
+        let request = self
+            .authorized(reqwest::Method::POST, url)
+            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
+            .json(&serde_json::json!({ "text": text }));
+        decode(self.send(request).await?)
+    }
+
+    pub async fn update(&self, id: &str, task: TaskUpdate) -> Result<Task> {
+        let path = "/tasks/".to_string().add(id);
+        self.post::<TaskUpdate, Task>(Some(task), path).await
+    }
+
+    pub async fn close(&self, id: &str) -> Result<()> {
+        let path = "/tasks/".to_string().add(id).add("/close");
+        self.post_empty::<()>(None, path).await
+    }
+
+    pub async fn reopen(&self, id: &str) -> Result<()> {
+        let path = "/tasks/".to_string().add(id).add("/reopen");
+        self.post_empty::<()>(None, path).await
+    }
+
+    /// Recently completed tasks, newest first. REST v2 only lists open
+    /// tasks, so this goes through the Sync API.
+    pub async fn completed_list(&self) -> Result<Vec<CompletedItem>> {
+        #[derive(serde::Deserialize)]
+        struct Completed {
+            items: Vec<CompletedItem>,
         }
+
+        let url = self.sync_base_url.to_owned().add("/completed/get_all");
+        let request = self.authorized(reqwest::Method::GET, url);
+        Ok(decode::<Completed>(self.send(request).await?)?.items)
     }
 
-    pub async fn view(&self, id: String) -> Result<Task, Box<dyn Error>> {
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let path = "/tasks/".to_string().add(id);
+        self.delete_resource(path).await
+    }
+
+    pub async fn view(&self, id: String) -> Result<Task> {
         let path: String = "/tasks/".to_string().add(&id);
-        return self.get::<Task>(path).await;
+        self.get::<Task>(path).await
     }
 
-    pub async fn project_view(&self, id: String) -> Result<Project, Box<dyn Error>> {
+    pub async fn project_view(&self, id: String) -> Result<Project> {
         let path: String = "/projects/".to_string().add(&id);
-        return self.get::<Project>(path).await;
+        self.get::<Project>(path).await
     }
 
-    pub async fn project_list(&self) -> Result<Vec<Project>, Box<dyn Error>> {
+    pub async fn project_list(&self) -> Result<Vec<Project>> {
         let path: String = "/projects".to_string();
-        return self.get::<Vec<Project>>(path).await;
+        self.get::<Vec<Project>>(path).await
+    }
+
+    pub async fn project_create(&self, project: ProjectCreate) -> Result<Project> {
+        self.post::<ProjectCreate, Project>(Some(project), String::from("/projects"))
+            .await
+    }
+
+    pub async fn project_update(&self, id: &str, project: ProjectUpdate) -> Result<Project> {
+        let path = "/projects/".to_string().add(id);
+        self.post::<ProjectUpdate, Project>(Some(project), path)
+            .await
+    }
+
+    /// Moves the project under `parent_id`, or to the top level if `None`.
+    /// REST v2 cannot change the parent, so this goes through the Sync API.
+    pub async fn project_move(&self, id: &str, parent_id: Option<&str>) -> Result<()> {
+        self.sync_command(
+            "project_move",
+            serde_json::json!({ "id": id, "parent_id": parent_id }),
+        )
+        .await
+    }
+
+    /// Archives the project together with its sub-projects.
+    pub async fn project_archive(&self, id: &str) -> Result<()> {
+        let path = "/projects/".to_string().add(id).add("/archive");
+        self.post_empty::<()>(None, path).await
+    }
+
+    pub async fn project_unarchive(&self, id: &str) -> Result<()> {
+        let path = "/projects/".to_string().add(id).add("/unarchive");
+        self.post_empty::<()>(None, path).await
+    }
+
+    /// Deletes the project together with its sub-projects, sections and tasks.
+    pub async fn project_delete(&self, id: &str) -> Result<()> {
+        let path = "/projects/".to_string().add(id);
+        self.delete_resource(path).await
+    }
+
+    /// Lists the sections of a single project, or of all projects if `project_id` is `None`.
+    pub async fn section_list(&self, project_id: Option<&str>) -> Result<Vec<Section>> {
+        let mut path: String = "/sections".to_string();
+        if let Some(id) = project_id {
+            let query = url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("project_id", id)
+                .finish();
+            path = path.add("?").add(&query);
+        }
+        self.get::<Vec<Section>>(path).await
     }
 
-    pub async fn label_list(&self) -> Result<Vec<Label>, Box<dyn Error>> {
+    pub async fn section_view(&self, id: &str) -> Result<Section> {
+        let path = "/sections/".to_string().add(id);
+        self.get::<Section>(path).await
+    }
+
+    pub async fn section_create(&self, section: SectionCreate) -> Result<Section> {
+        self.post::<SectionCreate, Section>(Some(section), String::from("/sections"))
+            .await
+    }
+
+    pub async fn section_update(&self, id: &str, name: String) -> Result<Section> {
+        let path = "/sections/".to_string().add(id);
+        self.post::<_, Section>(Some(serde_json::json!({ "name": name })), path)
+            .await
+    }
+
+    pub async fn section_delete(&self, id: &str) -> Result<()> {
+        let path = "/sections/".to_string().add(id);
+        self.delete_resource(path).await
+    }
+
+    pub async fn comment_list(&self, target: &CommentTarget) -> Result<Vec<Comment>> {
+        let path = "/comments".to_string().add(&target.to_query());
+        self.get::<Vec<Comment>>(path).await
+    }
+
+    pub async fn comment_view(&self, id: &str) -> Result<Comment> {
+        let path = "/comments/".to_string().add(id);
+        self.get::<Comment>(path).await
+    }
+
+    pub async fn comment_create(&self, comment: CommentCreate) -> Result<Comment> {
+        self.post::<CommentCreate, Comment>(Some(comment), String::from("/comments"))
+            .await
+    }
+
+    pub async fn comment_update(&self, id: &str, content: String) -> Result<Comment> {
+        let path = "/comments/".to_string().add(id);
+        self.post::<_, Comment>(Some(serde_json::json!({ "content": content })), path)
+            .await
+    }
+
+    pub async fn comment_delete(&self, id: &str) -> Result<()> {
+        let path = "/comments/".to_string().add(id);
+        self.delete_resource(path).await
+    }
+
+    pub async fn label_list(&self) -> Result<Vec<Label>> {
         let path = "/labels";
 
-        return self.get::<Vec<Label>>(path.to_owned()).await;
+        self.get::<Vec<Label>>(path.to_owned()).await
     }
 
-    async fn get<T: DeserializeOwned>(&self, sub_path: String) -> Result<T, Box<dyn Error>> {
-        let path = "https://api.todoist.com/rest/v2".to_string().add(&sub_path);
+    pub async fn label_view(&self, id: &str) -> Result<Label> {
+        let path = "/labels/".to_string().add(id);
+        self.get::<Label>(path).await
+    }
 
-        let resp = self
-            .http_client
-            .get(path)
-            .header(
-                self.bearer_token.0.to_owned(),
-                self.bearer_token.1.to_owned(),
-            )
-            .send()
-            .await?
-            .json::<T>()
-            .await?;
+    pub async fn label_create(&self, label: LabelCreate) -> Result<Label> {
+        self.post::<LabelCreate, Label>(Some(label), String::from("/labels"))
+            .await
+    }
+
+    pub async fn label_update(&self, id: &str, label: LabelUpdate) -> Result<Label> {
+        let path = "/labels/".to_string().add(id);
+        self.post::<LabelUpdate, Label>(Some(label), path).await
+    }
+
+    pub async fn label_delete(&self, id: &str) -> Result<()> {
+        let path = "/labels/".to_string().add(id);
+        self.delete_resource(path).await
+    }
+
+    /// Names of the labels used on shared tasks, including ones that are not personal labels.
+    pub async fn shared_label_list(&self) -> Result<Vec<String>> {
+        self.get::<Vec<String>>(String::from("/labels/shared"))
+            .await
+    }
 
-        Ok(resp)
+    /// Renames a shared label on every task it is used on.
+    pub async fn shared_label_rename(&self, name: &str, new_name: &str) -> Result<()> {
+        self.post_empty(
+            Some(serde_json::json!({ "name": name, "new_name": new_name })),
+            String::from("/labels/shared/rename"),
+        )
+        .await
     }
 
-    async fn post<B, T>(&self, body: Option<B>, sub_path: String) -> Result<T, Box<dyn Error>>
+    /// Removes a shared label from every task it is used on.
+    pub async fn shared_label_remove(&self, name: &str) -> Result<()> {
+        self.post_empty(
+            Some(serde_json::json!({ "name": name })),
+            String::from("/labels/shared/remove"),
+        )
+        .await
+    }
+
+    fn url(&self, sub_path: String) -> String {
+        self.base_url.to_owned().add(&sub_path)
+    }
+
+    /// Runs a single Sync API command, a command Todoist refuses fails like
+    /// a REST request answered with the same status.
+    async fn sync_command(&self, kind: &str, args: serde_json::Value) -> Result<()> {
+        let uuid = uuid::Uuid::new_v4().to_string();
+        let url = self.sync_base_url.to_owned().add("/sync");
+        let request = self
+            .authorized(reqwest::Method::POST, url)
+            .json(&serde_json::json!({
+                "commands": [{ "type": kind, "uuid": uuid, "args": args }]
+            }));
+        let body = self.send(request).await?;
+
+        let mut response = decode::<SyncResponse>(body.to_owned())?;
+        match response.sync_status.remove(&uuid) {
+            Some(SyncStatus::Ok(status)) if status == "ok" => Ok(()),
+            Some(SyncStatus::Failed { error, http_code }) => Err(Error::from_response(
+                StatusCode::from_u16(http_code).unwrap_or(StatusCode::BAD_REQUEST),
+                &HeaderMap::new(),
+                error,
+            )),
+            _ => Err(Error::Decode {
+                source: serde::de::Error::custom(format!("no status for command {uuid}")),
+                body,
+            }),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, sub_path: String) -> RequestBuilder {
+        self.authorized(method, self.url(sub_path))
+    }
+
+    fn authorized(&self, method: reqwest::Method, url: String) -> RequestBuilder {
+        self.http_client.request(method, url).header(
+            self.bearer_token.0.to_owned(),
+            self.bearer_token.1.to_owned(),
+        )
+    }
+
+    async fn get<T: DeserializeOwned>(&self, sub_path: String) -> Result<T> {
+        let body = self
+            .send(self.request(reqwest::Method::GET, sub_path))
+            .await?;
+        decode(body)
+    }
+
+    async fn post<B, T>(&self, body: Option<B>, sub_path: String) -> Result<T>
     where
         B: Serialize,
         T: DeserializeOwned,
     {
-        let path: String = "https://api.todoist.com/rest/v2".to_string().add(&sub_path);
+        let body = self.send(self.post_request(body, sub_path)).await?;
+        decode(body)
+    }
 
-        let mut request = self.http_client.post(path).header(
-            self.bearer_token.0.to_owned(),
-            self.bearer_token.1.to_owned(),
-        );
+    /// Same as `post`, for endpoints answering with `204 No Content`.
+    async fn post_empty<B: Serialize>(&self, body: Option<B>, sub_path: String) -> Result<()> {
+        self.send(self.post_request(body, sub_path)).await?;
+        Ok(())
+    }
+
+    async fn delete_resource(&self, sub_path: String) -> Result<()> {
+        self.send(self.request(reqwest::Method::DELETE, sub_path))
+            .await?;
+        Ok(())
+    }
+
+    /// Every POST carries a fresh `X-Request-Id`, Todoist uses it to drop
+    /// duplicates so the request can be retried safely.
+    fn post_request<B: Serialize>(&self, body: Option<B>, sub_path: String) -> RequestBuilder {
+        let mut request = self
+            .request(reqwest::Method::POST, sub_path)
+            .header("X-Request-Id", uuid::Uuid::new_v4().to_string());
 
-        if body.is_some() {
+        if let Some(body) = body {
             request = request.json(&body)
         }
+        request
+    }
+
+    /// Sends the request and returns the response body, retrying rate
+    /// limited, failed and unreachable requests according to the retry policy.
+    async fn send(&self, request: RequestBuilder) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            let result = match request.try_clone() {
+                Some(r) => send_once(r).await,
+                None => return send_once(request).await,
+            };
+
+            match result {
+                Err(err) => match self.retry.delay(attempt, &err) {
+                    Some(delay) => {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => return Err(err),
+                },
+                ok => return ok,
+            }
+        }
+    }
+}
 
-        let resp = request.send().await?.json::<T>().await?;
-        Ok(resp)
+#[derive(Deserialize)]
+struct SyncResponse {
+    sync_status: HashMap<String, SyncStatus>,
+}
+
+/// Outcome of a Sync API command, `"ok"` or an error object.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SyncStatus {
+    Ok(String),
+    Failed { error: String, http_code: u16 },
+}
+
+/// Sends the request once, turning every unsuccessful status into an `Error`.
+async fn send_once(request: RequestBuilder) -> Result<String> {
+    let resp = request.send().await?;
+    let status = resp.status();
+    let headers = resp.headers().to_owned();
+    let body = resp.text().await?;
+
+    if !status.is_success() {
+        return Err(Error::from_response(status, &headers, body));
     }
+    Ok(body)
+}
+
+fn decode<T: DeserializeOwned>(body: String) -> Result<T> {
+    serde_json::from_str(&body).map_err(|source| Error::Decode { source, body })
 }
@@ -0,0 +1,50 @@
+/// Names of the colors Todoist accepts for projects and labels.
+pub const COLORS: [&str; 20] = [
+    "berry_red",
+    "red",
+    "orange",
+    "yellow",
+    "olive_green",
+    "lime_green",
+    "green",
+    "mint_green",
+    "teal",
+    "sky_blue",
+    "light_blue",
+    "blue",
+    "grape",
+    "violet",
+    "lavender",
+    "magenta",
+    "salmon",
+    "charcoal",
+    "grey",
+    "taupe",
+];
+
+/// RGB value Todoist uses for the named color.
+pub fn rgb(name: &str) -> Option<(u8, u8, u8)> {
+    match name {
+        "berry_red" => Some((0xb8, 0x25, 0x6f)),
+        "red" => Some((0xdb, 0x40, 0x35)),
+        "orange" => Some((0xff, 0x99, 0x33)),
+        "yellow" => Some((0xfa, 0xd0, 0x00)),
+        "olive_green" => Some((0xaf, 0xb8, 0x3b)),
+        "lime_green" => Some((0x7e, 0xcc, 0x49)),
+        "green" => Some((0x29, 0x94, 0x38)),
+        "mint_green" => Some((0x6a, 0xcc, 0xbc)),
+        "teal" => Some((0x15, 0x8f, 0xad)),
+        "sky_blue" => Some((0x14, 0xaa, 0xf5)),
+        "light_blue" => Some((0x96, 0xc3, 0xeb)),
+        "blue" => Some((0x40, 0x73, 0xff)),
+        "grape" => Some((0x88, 0x4d, 0xff)),
+        "violet" => Some((0xaf, 0x38, 0xeb)),
+        "lavender" => Some((0xeb, 0x96, 0xeb)),
+        "magenta" => Some((0xe0, 0x51, 0x94)),
+        "salmon" => Some((0xff, 0x8d, 0x85)),
+        "charcoal" => Some((0x80, 0x80, 0x80)),
+        "grey" => Some((0xb8, 0xb8, 0xb8)),
+        "taupe" => Some((0xcc, 0xac, 0x93)),
+        _ => None,
+    }
+}
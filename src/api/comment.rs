@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    #[serde(rename = "task_id")]
+    pub task_id: Option<String>,
+    #[serde(rename = "project_id")]
+    pub project_id: Option<String>,
+    #[serde(rename = "posted_at")]
+    pub posted_at: DateTime<Utc>,
+    pub content: String,
+    pub attachment: Option<Value>,
+}
+
+/// What a comment thread belongs to, comments are either on a task or on a project.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommentTarget {
+    Task(String),
+    Project(String),
+}
+
+impl CommentTarget {
+    pub fn to_query(&self) -> String {
+        let (key, id) = match self {
+            CommentTarget::Task(id) => ("task_id", id),
+            CommentTarget::Project(id) => ("project_id", id),
+        };
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair(key, id)
+            .finish();
+        format!("?{query}")
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentCreate {
+    #[serde(rename = "task_id", skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(rename = "project_id", skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub content: String,
+}
+
+impl CommentCreate {
+    pub fn new(content: String, target: CommentTarget) -> CommentCreate {
+        let (task_id, project_id) = match target {
+            CommentTarget::Task(id) => (Some(id), None),
+            CommentTarget::Project(id) => (None, Some(id)),
+        };
+        CommentCreate {
+            task_id,
+            project_id,
+            content,
+        }
+    }
+}
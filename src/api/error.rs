@@ -1,21 +1,132 @@
 use core::fmt;
-use std::error::Error;
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
-pub struct RequestFailed {
-    err_code: String,
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong while talking to the Todoist API.
+///
+/// Variants created from an HTTP response carry the response body, since
+/// Todoist usually explains what went wrong there.
+#[derive(Debug)]
+pub enum Error {
+    /// The API token is missing, invalid or lacks access (401/403).
+    Unauthorized { body: String },
+    /// The requested resource does not exist (404).
+    NotFound { body: String },
+    /// Too many requests were made (429), `retry_after` comes from the `Retry-After` header.
+    RateLimited {
+        retry_after: Option<Duration>,
+        body: String,
+    },
+    /// Todoist failed to handle the request (5xx).
+    Server { status: StatusCode, body: String },
+    /// Any other unsuccessful status, usually a malformed request (4xx).
+    Request { status: StatusCode, body: String },
+    /// The request could not be sent or the response could not be read.
+    Network(reqwest::Error),
+    /// The response body did not match the expected shape.
+    Decode {
+        source: serde_json::Error,
+        body: String,
+    },
 }
 
-impl RequestFailed {
-    pub fn new(err_code: String) -> RequestFailed {
-        RequestFailed { err_code }
+impl Error {
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, body: String) -> Error {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::Unauthorized { body },
+            StatusCode::NOT_FOUND => Error::NotFound { body },
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimited {
+                retry_after: headers
+                    .get(RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs),
+                body,
+            },
+            s if s.is_server_error() => Error::Server { status, body },
+            _ => Error::Request { status, body },
+        }
+    }
+
+    /// Body of the response that caused the error, if there was one.
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            Error::Unauthorized { body }
+            | Error::NotFound { body }
+            | Error::RateLimited { body, .. }
+            | Error::Server { body, .. }
+            | Error::Request { body, .. }
+            | Error::Decode { body, .. } => Some(body),
+            Error::Network(_) => None,
+        }
+    }
+
+    /// Process exit code the CLI uses for this error, see the README for the full list.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Unauthorized { .. } => 3,
+            Error::NotFound { .. } => 4,
+            Error::RateLimited { .. } => 5,
+            Error::Server { .. } => 6,
+            Error::Request { .. } => 7,
+            Error::Network(_) => 8,
+            Error::Decode { .. } => 9,
+        }
     }
 }
 
-impl fmt::Display for RequestFailed {
+impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "the request failed with status code {}", self.err_code)
+        match self {
+            Error::Unauthorized { .. } => write!(
+                f,
+                "the API token was rejected, log in again with `todoist auth login`"
+            )?,
+            Error::NotFound { .. } => write!(f, "the requested resource was not found")?,
+            Error::RateLimited {
+                retry_after: Some(after),
+                ..
+            } => write!(
+                f,
+                "rate limited by Todoist, retry in {} seconds",
+                after.as_secs()
+            )?,
+            Error::RateLimited {
+                retry_after: None, ..
+            } => write!(f, "rate limited by Todoist, retry later")?,
+            Error::Server { status, .. } => {
+                write!(f, "Todoist failed to handle the request ({status})")?
+            }
+            Error::Request { status, .. } => write!(f, "the request was rejected ({status})")?,
+            Error::Network(e) => return write!(f, "failed to reach Todoist: {e}"),
+            Error::Decode { source, .. } => {
+                return write!(f, "failed to decode the response: {source}")
+            }
+        }
+
+        match self.body().map(str::trim) {
+            Some(body) if !body.is_empty() => write!(f, ": {body}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(e) => Some(e),
+            Error::Decode { source, .. } => Some(source),
+            _ => None,
+        }
     }
 }
 
-impl Error for RequestFailed {}
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Error {
+        Error::Network(e)
+    }
+}
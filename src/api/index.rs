@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use super::{Project, Section};
+
+/// Resolves the ids a task refers to into names, so listing many tasks does
+/// not need a request per task.
+#[derive(Default, Debug, Clone)]
+pub struct Index {
+    projects: HashMap<String, Project>,
+    sections: HashMap<String, Section>,
+}
+
+impl Index {
+    pub fn new(projects: Vec<Project>, sections: Vec<Section>) -> Index {
+        Index {
+            projects: projects.into_iter().map(|p| (p.id.to_owned(), p)).collect(),
+            sections: sections.into_iter().map(|s| (s.id.to_owned(), s)).collect(),
+        }
+    }
+
+    pub fn project(&self, id: &str) -> Option<&Project> {
+        self.projects.get(id)
+    }
+
+    /// Name of the project, or its id if the project is unknown.
+    pub fn project_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.project(id).map(|p| p.name.as_str()).unwrap_or(id)
+    }
+
+    pub fn section(&self, id: &str) -> Option<&Section> {
+        self.sections.get(id)
+    }
+
+    /// Name of the section, or its id if the section is unknown.
+    pub fn section_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.section(id).map(|s| s.name.as_str()).unwrap_or(id)
+    }
+}
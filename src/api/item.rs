@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::Priority;
+
+/// A task as the Sync API returns it, only the fields shared with `Task`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub id: String,
+    pub project_id: String,
+    pub section_id: Option<String>,
+    pub parent_id: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub priority: Priority,
+    pub due: Option<ItemDue>,
+}
+
+/// A completed task as the Sync API lists it.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletedItem {
+    pub task_id: String,
+    pub project_id: String,
+    pub content: String,
+    pub completed_at: String,
+}
+
+/// Due date of an `Item`. Unlike in the REST API, `date` holds the time too
+/// for tasks due at a specific time.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDue {
+    pub date: String,
+    pub string: String,
+    #[serde(default)]
+    pub is_recurring: bool,
+    pub timezone: Option<String>,
+    pub lang: Option<String>,
+}
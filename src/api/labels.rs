@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -8,3 +10,83 @@ pub struct Label {
     pub order: i64,
     pub is_favorite: bool,
 }
+
+impl Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelCreate {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_favorite: Option<bool>,
+}
+
+impl LabelCreate {
+    pub fn new(name: String) -> LabelCreate {
+        LabelCreate {
+            name,
+            ..LabelCreate::default()
+        }
+    }
+
+    pub fn color(&mut self, color: String) -> &mut LabelCreate {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn order(&mut self, order: i64) -> &mut LabelCreate {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn favorite(&mut self, is_favorite: bool) -> &mut LabelCreate {
+        self.is_favorite = Some(is_favorite);
+        self
+    }
+}
+
+/// Changes to apply to a personal label, fields left as `None` stay untouched.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_favorite: Option<bool>,
+}
+
+impl LabelUpdate {
+    pub fn new() -> LabelUpdate {
+        LabelUpdate::default()
+    }
+
+    pub fn name(&mut self, name: String) -> &mut LabelUpdate {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn color(&mut self, color: String) -> &mut LabelUpdate {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn order(&mut self, order: i64) -> &mut LabelUpdate {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn favorite(&mut self, is_favorite: bool) -> &mut LabelUpdate {
+        self.is_favorite = Some(is_favorite);
+        self
+    }
+}
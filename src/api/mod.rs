@@ -1,13 +1,27 @@
 mod client;
+mod color;
+mod comment;
 mod error;
+mod index;
+mod item;
 mod labels;
+mod priority;
 mod project;
+mod retry;
+mod section;
 mod task;
 
 pub use self::{
-    client::Client,
-    error::RequestFailed,
-    labels::Label,
-    project::Project,
-    task::{Task, TaskCreate, TaskFilter},
+    client::{Client, DEFAULT_BASE_URL, DEFAULT_SYNC_BASE_URL},
+    color::{rgb, COLORS},
+    comment::{Comment, CommentCreate, CommentTarget},
+    error::{Error, Result},
+    index::Index,
+    item::{CompletedItem, Item, ItemDue},
+    labels::{Label, LabelCreate, LabelUpdate},
+    priority::Priority,
+    project::{Project, ProjectCreate, ProjectUpdate, VIEW_STYLES},
+    retry::RetryPolicy,
+    section::{Section, SectionCreate},
+    task::{Due, Duration, DurationUnit, Task, TaskCreate, TaskFilter, TaskUpdate},
 };
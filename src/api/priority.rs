@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+/// Priority of a task as the Todoist apps show it, `P1` being the most
+/// urgent. The REST API numbers them the other way round, 4 is `P1`.
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Priority {
+    P1,
+    P2,
+    P3,
+    #[default]
+    P4,
+}
+
+impl Priority {
+    /// RGB value of the flag Todoist shows for the priority, `P4` has none.
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            Priority::P1 => Some((0xd1, 0x45, 0x3b)),
+            Priority::P2 => Some((0xeb, 0x89, 0x09)),
+            Priority::P3 => Some((0x24, 0x6f, 0xe0)),
+            Priority::P4 => None,
+        }
+    }
+
+    /// Flag task lists show for the priority, drawn in the `rgb` color.
+    pub fn flag_label(&self) -> String {
+        format!("⚑ {self}")
+    }
+}
+
+impl From<Priority> for u8 {
+    fn from(priority: Priority) -> u8 {
+        match priority {
+            Priority::P1 => 4,
+            Priority::P2 => 3,
+            Priority::P3 => 2,
+            Priority::P4 => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for Priority {
+    type Error = String;
+
+    fn try_from(value: u8) -> std::result::Result<Priority, String> {
+        match value {
+            4 => Ok(Priority::P1),
+            3 => Ok(Priority::P2),
+            2 => Ok(Priority::P3),
+            1 => Ok(Priority::P4),
+            _ => Err(format!("invalid priority {value}, expected 1 to 4")),
+        }
+    }
+}
+
+impl Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Priority::P1 => write!(f, "p1"),
+            Priority::P2 => write!(f, "p2"),
+            Priority::P3 => write!(f, "p3"),
+            Priority::P4 => write!(f, "p4"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    /// Accepts the app's notation (`p1`, `1`) and names (`urgent`, `high`,
+    /// `medium`, `low`), `1` being the most urgent.
+    fn from_str(s: &str) -> std::result::Result<Priority, String> {
+        match s.trim().to_lowercase().as_str() {
+            "p1" | "1" | "urgent" => Ok(Priority::P1),
+            "p2" | "2" | "high" => Ok(Priority::P2),
+            "p3" | "3" | "medium" => Ok(Priority::P3),
+            "p4" | "4" | "low" | "normal" => Ok(Priority::P4),
+            _ => Err(format!(
+                "unknown priority `{s}`, expected p1 to p4, 1 to 4, urgent, high, medium or low"
+            )),
+        }
+    }
+}
@@ -31,3 +31,92 @@ impl Display for Project {
         write!(f, "{}", self.name)
     }
 }
+
+/// Layouts a project can be shown in.
+pub const VIEW_STYLES: [&str; 3] = ["list", "board", "calendar"];
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectCreate {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_favorite: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view_style: Option<String>,
+}
+
+impl ProjectCreate {
+    pub fn new(name: String) -> ProjectCreate {
+        ProjectCreate {
+            name,
+            ..ProjectCreate::default()
+        }
+    }
+
+    pub fn parent(&mut self, id: String) -> &mut ProjectCreate {
+        self.parent_id = Some(id);
+        self
+    }
+
+    pub fn color(&mut self, color: String) -> &mut ProjectCreate {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn favorite(&mut self, is_favorite: bool) -> &mut ProjectCreate {
+        self.is_favorite = Some(is_favorite);
+        self
+    }
+
+    pub fn view_style(&mut self, view_style: String) -> &mut ProjectCreate {
+        self.view_style = Some(view_style);
+        self
+    }
+}
+
+/// Changes to apply to a project, fields left as `None` stay untouched.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_favorite: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view_style: Option<String>,
+}
+
+impl ProjectUpdate {
+    pub fn new() -> ProjectUpdate {
+        ProjectUpdate::default()
+    }
+
+    /// Returns true if the update would not change anything.
+    pub fn is_empty(&self) -> bool {
+        *self == ProjectUpdate::default()
+    }
+
+    pub fn name(&mut self, name: String) -> &mut ProjectUpdate {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn color(&mut self, color: String) -> &mut ProjectUpdate {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn favorite(&mut self, is_favorite: bool) -> &mut ProjectUpdate {
+        self.is_favorite = Some(is_favorite);
+        self
+    }
+
+    pub fn view_style(&mut self, view_style: String) -> &mut ProjectUpdate {
+        self.view_style = Some(view_style);
+        self
+    }
+}
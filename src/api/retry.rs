@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::Error;
+
+/// How often and how long the client waits before retrying a failed request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt, `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every following one.
+    pub base_delay_ms: u64,
+    /// Upper bound for a single delay, a longer `Retry-After` is not waited for.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 30_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Returns how long to wait before retrying after `err`, or `None` if
+    /// the request should not be retried.
+    pub(crate) fn delay(&self, attempt: u32, err: &Error) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let max_delay = Duration::from_millis(self.max_delay_ms);
+        match err {
+            Error::RateLimited {
+                retry_after: Some(after),
+                ..
+            } => (*after <= max_delay).then_some(*after),
+            Error::RateLimited { .. } | Error::Server { .. } | Error::Network(_) => {
+                Some(self.backoff(attempt).min(max_delay))
+            }
+            _ => None,
+        }
+    }
+
+    /// Exponential backoff with "equal jitter": half of the delay is fixed,
+    /// the other half random, so concurrent clients spread out.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt))
+            .min(self.max_delay_ms);
+        let half = delay / 2;
+        Duration::from_millis(half + rand::thread_rng().gen_range(0..=delay - half))
+    }
+}
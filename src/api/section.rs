@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Section {
+    pub id: String,
+    #[serde(rename = "project_id")]
+    pub project_id: String,
+    pub order: i64,
+    pub name: String,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionCreate {
+    pub name: String,
+    #[serde(rename = "project_id")]
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+}
+
+impl SectionCreate {
+    pub fn new(name: String, project_id: String) -> SectionCreate {
+        SectionCreate {
+            name,
+            project_id,
+            order: None,
+        }
+    }
+
+    pub fn order(&mut self, order: i64) -> &mut SectionCreate {
+        self.order = Some(order);
+        self
+    }
+}
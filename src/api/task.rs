@@ -1,8 +1,9 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
-use std::{error::Error, ops::Add};
 
-use super::{Client, Project};
+use super::Priority;
+use std::{fmt::Display, ops::Add, str::FromStr};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,53 +16,148 @@ pub struct Task {
     #[serde(rename = "project_id")]
     pub project_id: String,
     #[serde(rename = "section_id")]
-    pub section_id: Value,
+    pub section_id: Option<String>,
     #[serde(rename = "parent_id")]
-    pub parent_id: Value,
+    pub parent_id: Option<String>,
     pub order: i64,
     pub content: String,
     pub description: String,
     #[serde(rename = "is_completed")]
     pub is_completed: bool,
     pub labels: Vec<String>,
-    pub priority: i64,
+    pub priority: Priority,
     #[serde(rename = "comment_count")]
     pub comment_count: i64,
     #[serde(rename = "creator_id")]
     pub creator_id: String,
     #[serde(rename = "created_at")]
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
     pub due: Option<Due>,
+    #[serde(default)]
+    pub duration: Option<Duration>,
     pub url: String,
 }
 
-impl Task {
-    pub async fn project(&self, client: &Client) -> Result<Project, Box<dyn Error>> {
-        return client.project_view(self.project_id.to_string()).await;
-    }
-}
-
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Due {
-    pub date: String,
+    pub date: NaiveDate,
+    /// Set for tasks due at a specific time. Floating times, which the API
+    /// sends without an offset, are taken as local time.
+    #[serde(default, deserialize_with = "deserialize_datetime")]
+    pub datetime: Option<DateTime<Utc>>,
     pub string: String,
     pub lang: Option<String>,
     #[serde(rename = "is_recurring")]
     pub is_recurring: bool,
+    /// Timezone the due time is fixed to, e.g. `Europe/Riga`.
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
+impl Due {
+    /// When the task is due, the start of the day for tasks without a time.
+    pub fn at(&self) -> DateTime<Local> {
+        match self.datetime {
+            Some(datetime) => datetime.with_timezone(&Local),
+            None => local(self.date.and_hms_opt(0, 0, 0).unwrap_or_default()),
+        }
+    }
+
+    /// Tasks with a time are overdue once it has passed, all-day tasks only
+    /// once their day is over.
+    pub fn is_overdue(&self, now: DateTime<Local>) -> bool {
+        match self.datetime {
+            Some(datetime) => datetime < now,
+            None => self.date < now.date_naive(),
+        }
+    }
+}
+
+fn local(datetime: NaiveDateTime) -> DateTime<Local> {
+    Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&datetime))
+}
+
+fn deserialize_datetime<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(value) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(&value) {
+        return Ok(Some(datetime.with_timezone(&Utc)));
+    }
+    NaiveDateTime::parse_from_str(&value, "%Y-%m-%dT%H:%M:%S%.f")
+        .map(|datetime| Some(local(datetime).with_timezone(&Utc)))
+        .map_err(serde::de::Error::custom)
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Duration {
+    pub amount: u32,
+    pub unit: DurationUnit,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationUnit {
+    #[default]
+    Minute,
+    Day,
+}
+
+impl Display for DurationUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationUnit::Minute => write!(f, "minute"),
+            DurationUnit::Day => write!(f, "day"),
+        }
+    }
+}
+
+impl FromStr for DurationUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<DurationUnit, String> {
+        match s {
+            "minute" => Ok(DurationUnit::Minute),
+            "day" => Ok(DurationUnit::Day),
+            _ => Err(format!(
+                "unknown duration unit `{s}`, expected `minute` or `day`"
+            )),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct TaskFilter {
+    /// Filter in the Todoist query syntax, e.g. `today|overdue`.
     pub day_filter: Option<String>,
+    pub project_id: Option<String>,
 }
 
 impl TaskFilter {
-    pub fn to_string(self) -> String {
-        let mut query: String = String::from("?filter=");
-        if let Some(day_filter) = self.day_filter {
-            query = query.add(&day_filter.to_string());
+    pub fn to_query(&self) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(day_filter) = &self.day_filter {
+            query.append_pair("filter", day_filter);
+        }
+        if let Some(project_id) = &self.project_id {
+            query.append_pair("project_id", project_id);
+        }
+
+        let query = query.finish();
+        if query.is_empty() {
+            query
+        } else {
+            String::from("?").add(&query)
         }
-        return query;
     }
 }
 
@@ -69,42 +165,145 @@ impl TaskFilter {
 #[serde(rename_all = "camelCase")]
 pub struct TaskCreate {
     pub content: String,
-    #[serde(rename = "due_string")]
+    #[serde(rename = "due_string", skip_serializing_if = "Option::is_none")]
     pub due_string: Option<String>,
-    pub priority: Option<u8>,
-    #[serde(rename = "project_id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(rename = "project_id", skip_serializing_if = "Option::is_none")]
     pub project_id: Option<String>,
+    #[serde(rename = "section_id", skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    #[serde(rename = "parent_id", skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub labels: Option<Vec<String>>,
 }
 
 impl TaskCreate {
     pub fn new(content: String) -> TaskCreate {
-        return TaskCreate {
+        TaskCreate {
             content,
             due_string: None,
             priority: None,
             project_id: None,
+            section_id: None,
+            parent_id: None,
             labels: None,
-        };
+        }
     }
 
-    pub fn due<'a>(&'a mut self, date: String) -> &'a mut TaskCreate {
+    pub fn due(&mut self, date: String) -> &mut TaskCreate {
         self.due_string = Some(date);
         self
     }
 
-    pub fn priority<'a>(&'a mut self, priority: u8) -> &'a mut TaskCreate {
+    pub fn priority(&mut self, priority: Priority) -> &mut TaskCreate {
         self.priority = Some(priority);
         self
     }
 
-    pub fn project<'a>(&'a mut self, id: String) -> &'a mut TaskCreate {
+    pub fn project(&mut self, id: String) -> &mut TaskCreate {
         self.project_id = Some(id);
         self
     }
 
-    pub fn labels<'a>(&'a mut self, labels: Vec<String>) -> &'a mut TaskCreate {
+    pub fn section(&mut self, id: String) -> &mut TaskCreate {
+        self.section_id = Some(id);
+        self
+    }
+
+    /// Makes the task a sub-task of the task with `id`.
+    pub fn parent(&mut self, id: String) -> &mut TaskCreate {
+        self.parent_id = Some(id);
+        self
+    }
+
+    pub fn labels(&mut self, labels: Vec<String>) -> &mut TaskCreate {
         self.labels = Some(labels);
         self
     }
 }
+
+/// Changes to apply to an existing task, fields left as `None` are not sent
+/// and stay untouched.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "due_string", skip_serializing_if = "Option::is_none")]
+    pub due_string: Option<String>,
+    #[serde(rename = "due_date", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    #[serde(rename = "due_datetime", skip_serializing_if = "Option::is_none")]
+    pub due_datetime: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(rename = "assignee_id", skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+    #[serde(rename = "duration_unit", skip_serializing_if = "Option::is_none")]
+    pub duration_unit: Option<DurationUnit>,
+}
+
+impl TaskUpdate {
+    pub fn new() -> TaskUpdate {
+        TaskUpdate::default()
+    }
+
+    /// Returns true if the update would not change anything.
+    pub fn is_empty(&self) -> bool {
+        *self == TaskUpdate::default()
+    }
+
+    pub fn content(&mut self, content: String) -> &mut TaskUpdate {
+        self.content = Some(content);
+        self
+    }
+
+    pub fn description(&mut self, description: String) -> &mut TaskUpdate {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn due(&mut self, date: String) -> &mut TaskUpdate {
+        self.due_string = Some(date);
+        self
+    }
+
+    pub fn due_date(&mut self, date: NaiveDate) -> &mut TaskUpdate {
+        self.due_date = Some(date);
+        self
+    }
+
+    pub fn due_datetime(&mut self, datetime: DateTime<FixedOffset>) -> &mut TaskUpdate {
+        self.due_datetime = Some(datetime);
+        self
+    }
+
+    pub fn priority(&mut self, priority: Priority) -> &mut TaskUpdate {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn labels(&mut self, labels: Vec<String>) -> &mut TaskUpdate {
+        self.labels = Some(labels);
+        self
+    }
+
+    pub fn assignee(&mut self, id: String) -> &mut TaskUpdate {
+        self.assignee_id = Some(id);
+        self
+    }
+
+    pub fn duration(&mut self, amount: u32, unit: DurationUnit) -> &mut TaskUpdate {
+        self.duration = Some(amount);
+        self.duration_unit = Some(unit);
+        self
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::Display;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+
+/// Data that changes rarely and is kept on disk between runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Projects,
+    Labels,
+    Sections,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 3] = [Kind::Projects, Kind::Labels, Kind::Sections];
+
+    fn file_name(&self) -> String {
+        format!("{self}.json")
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Projects => write!(f, "projects"),
+            Kind::Labels => write!(f, "labels"),
+            Kind::Sections => write!(f, "sections"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<I> {
+    /// Seconds since the unix epoch.
+    fetched_at: u64,
+    items: I,
+}
+
+/// State of a single cache file as shown by `cache status`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Status {
+    pub kind: String,
+    pub items: usize,
+    /// Seconds since the data was fetched.
+    pub age_secs: u64,
+    pub fresh: bool,
+}
+
+/// On-disk cache of projects, labels and sections.
+///
+/// Reading and writing the cache is best effort, a missing or broken cache
+/// file just means the data is fetched from the API again.
+pub struct Store {
+    dir: PathBuf,
+    ttl: Duration,
+    read: bool,
+}
+
+impl Store {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Store {
+        Store {
+            dir,
+            ttl,
+            read: true,
+        }
+    }
+
+    /// `~/.cache/todoist` on Linux, the platform equivalent elsewhere.
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("todoist")
+    }
+
+    /// Stops reading from the cache, fetched data is still written to it.
+    pub fn bypass(mut self) -> Store {
+        self.read = false;
+        self
+    }
+
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    pub async fn projects(&self, client: &api::Client) -> api::Result<Vec<api::Project>> {
+        self.cached(Kind::Projects, || client.project_list()).await
+    }
+
+    pub async fn labels(&self, client: &api::Client) -> api::Result<Vec<api::Label>> {
+        self.cached(Kind::Labels, || client.label_list()).await
+    }
+
+    /// Sections of all projects.
+    pub async fn sections(&self, client: &api::Client) -> api::Result<Vec<api::Section>> {
+        self.cached(Kind::Sections, || client.section_list(None))
+            .await
+    }
+
+    /// Builds a name index from the cached projects and sections.
+    pub async fn index(&self, client: &api::Client) -> api::Result<api::Index> {
+        Ok(api::Index::new(
+            self.projects(client).await?,
+            self.sections(client).await?,
+        ))
+    }
+
+    /// Fetches everything again, regardless of how old the cache is.
+    pub async fn refresh(&self, client: &api::Client) -> api::Result<()> {
+        self.write(Kind::Projects, &client.project_list().await?);
+        self.write(Kind::Labels, &client.label_list().await?);
+        self.write(Kind::Sections, &client.section_list(None).await?);
+        Ok(())
+    }
+
+    /// Drops the cached data of `kind`, used after it was changed.
+    pub fn invalidate(&self, kind: Kind) {
+        let _ = fs::remove_file(self.dir.join(kind.file_name()));
+    }
+
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn status(&self) -> Vec<Status> {
+        Kind::ALL
+            .iter()
+            .filter_map(|kind| {
+                let entry = self.read_entry::<serde_json::Value>(*kind)?;
+                let age = age(entry.fetched_at);
+                Some(Status {
+                    kind: kind.to_string(),
+                    items: entry.items.len(),
+                    age_secs: age.as_secs(),
+                    fresh: age < self.ttl,
+                })
+            })
+            .collect()
+    }
+
+    async fn cached<T, F, Fut>(&self, kind: Kind, fetch: F) -> api::Result<Vec<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = api::Result<Vec<T>>>,
+    {
+        if self.read {
+            if let Some(entry) = self.read_entry::<T>(kind) {
+                if age(entry.fetched_at) < self.ttl {
+                    return Ok(entry.items);
+                }
+            }
+        }
+
+        let items = fetch().await?;
+        self.write(kind, &items);
+        Ok(items)
+    }
+
+    fn read_entry<T: DeserializeOwned>(&self, kind: Kind) -> Option<Entry<Vec<T>>> {
+        let content = fs::read_to_string(self.dir.join(kind.file_name())).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn write<T: Serialize>(&self, kind: Kind, items: &[T]) {
+        let entry = Entry {
+            fetched_at: now(),
+            items,
+        };
+        if let Ok(content) = serde_json::to_string(&entry) {
+            let _ = fs::create_dir_all(&self.dir)
+                .and_then(|_| fs::write(self.dir.join(kind.file_name()), content));
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn age(fetched_at: u64) -> Duration {
+    Duration::from_secs(now().saturating_sub(fetched_at))
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use crate::{api, config, oauth, secret::Secrets};
+
+use super::{Error, Prompt, Result};
+
+/// Commands that store or remove the API token, in the config file or the
+/// secret backend it selects.
+pub struct Auth {
+    path: PathBuf,
+    profile: Option<String>,
+    vars: config::Vars,
+    prompt: Prompt,
+}
+
+impl Auth {
+    /// The passphrase of the secrets file is taken from `vars`, it is
+    /// prompted for if needed and not given.
+    pub fn new(path: PathBuf, profile: Option<String>, vars: config::Vars, prompt: Prompt) -> Auth {
+        Auth {
+            path,
+            profile,
+            vars,
+            prompt,
+        }
+    }
+
+    /// Stores the API token after checking that Todoist accepts it, prompts
+    /// for it if not given.
+    pub async fn login(&self, token: &Option<String>) -> Result<()> {
+        let token = match token {
+            Some(x) => x.to_owned(),
+            None => self
+                .prompt
+                .password("API token (Settings > Integrations > Developer)", "--token")?,
+        };
+
+        let config = self.config()?;
+        let client = api::Client::new(
+            reqwest::Client::new(),
+            token.to_owned(),
+            config.base_url.to_owned(),
+        );
+        let projects = client.project_list().await?;
+
+        self.store(&config, &token)?;
+        println!("logged in to an account with {} projects", projects.len());
+        Ok(())
+    }
+
+    /// Logs in through the browser with the Todoist app from the config,
+    /// `client_id` and `client_secret` replace the configured ones.
+    pub async fn oauth(
+        &self,
+        client_id: &Option<String>,
+        client_secret: &Option<String>,
+        browser: bool,
+    ) -> Result<()> {
+        let config = self.config()?;
+        let mut settings = config.oauth.to_owned();
+        if let Some(x) = client_id {
+            settings.client_id = Some(x.to_owned());
+        }
+        if let Some(x) = client_secret {
+            settings.client_secret = Some(x.to_owned());
+        }
+
+        let token = oauth::authorize(&reqwest::Client::new(), &settings, |url| {
+            eprintln!("Open this URL to log in:\n{url}");
+            // The URL is printed in case there is no browser to open.
+            if browser {
+                let _ = open::that_detached(url.as_str());
+            }
+        })
+        .await?;
+
+        self.store(&config, &token)?;
+        println!("logged in");
+        Ok(())
+    }
+
+    /// Removes the stored API token.
+    pub fn logout(&self) -> Result<()> {
+        let config = self.config()?;
+        let removed = match self.secrets(&config)? {
+            Some(secrets) => secrets
+                .delete(config.account())
+                .map_err(|e| Error::Config(e.into()))?,
+            None => self.unset_api_key()?,
+        };
+        println!(
+            "{}",
+            if removed {
+                "logged out"
+            } else {
+                "not logged in"
+            }
+        );
+        Ok(())
+    }
+
+    /// Saves `token` where the config says, only keeping it in the config
+    /// file if no secret backend is used.
+    fn store(&self, config: &config::Config, token: &str) -> Result<()> {
+        match self.secrets(config)? {
+            Some(secrets) => {
+                secrets
+                    .set(config.account(), token)
+                    .map_err(|e| Error::Config(e.into()))?;
+                self.unset_api_key()?;
+            }
+            None => config::set(&self.path, self.profile.as_deref(), "api_key", token)
+                .map_err(Error::Config)?,
+        }
+        Ok(())
+    }
+
+    fn config(&self) -> Result<config::Config> {
+        config::load(&self.path, self.profile.as_deref(), &self.vars).map_err(Error::Config)
+    }
+
+    fn secrets(&self, config: &config::Config) -> Result<Option<Secrets>> {
+        let passphrase = match self.vars.get(config::PASSPHRASE_ENV) {
+            Some(x) => Some(x.to_owned()),
+            None if config.secret_backend == crate::secret::Backend::File => Some(
+                self.prompt
+                    .password("Passphrase of the secrets file", config::PASSPHRASE_ENV)?,
+            ),
+            None => None,
+        };
+        config
+            .secrets(&self.path, passphrase)
+            .map_err(Error::Config)
+    }
+
+    fn unset_api_key(&self) -> Result<bool> {
+        config::unset(&self.path, self.profile.as_deref(), "api_key").map_err(Error::Config)
+    }
+}
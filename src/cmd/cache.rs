@@ -0,0 +1,69 @@
+use std::io;
+
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+
+use crate::api;
+use crate::cache::Store;
+
+use super::output::{self, Output};
+use super::Result;
+
+pub struct Cache<'a> {
+    pub(super) client: &'a api::Client,
+    pub(super) store: &'a Store,
+    pub(super) output: Output,
+}
+
+impl Cache<'_> {
+    pub async fn refresh(&self) -> Result<()> {
+        self.store.refresh(self.client).await?;
+        println!("cache refreshed");
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        self.store.clear().map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to clear {}: {}", self.store.dir().display(), e),
+            )
+        })?;
+        println!("cache cleared");
+        Ok(())
+    }
+
+    pub fn status(&self) -> Result<()> {
+        let status = self.store.status();
+        if !self.output.is_table() {
+            output::print_items(self.output, &status)?;
+            return Ok(());
+        }
+
+        println!("{}", self.store.dir().display());
+        let mut table = Table::new();
+        table
+            .set_header(vec!["Cache", "Items", "Fetched", "State"])
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS);
+
+        for entry in status {
+            table.add_row(vec![
+                entry.kind,
+                entry.items.to_string(),
+                format!("{} ago", human_duration(entry.age_secs)),
+                String::from(if entry.fresh { "fresh" } else { "stale" }),
+            ]);
+        }
+        println!("{table}");
+        Ok(())
+    }
+}
+
+fn human_duration(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{s}s"),
+        s if s < 60 * 60 => format!("{}m", s / 60),
+        s if s < 24 * 60 * 60 => format!("{}h", s / (60 * 60)),
+        s => format!("{}d", s / (24 * 60 * 60)),
+    }
+}
@@ -0,0 +1,79 @@
+use yansi::Paint;
+
+use crate::api;
+
+use super::output::{self, Output};
+use super::{Prompt, Result};
+
+pub struct Comments<'a> {
+    pub(super) client: &'a api::Client,
+    pub(super) output: Output,
+    pub(super) prompt: Prompt,
+}
+
+impl Comments<'_> {
+    pub async fn list(&self, target: &api::CommentTarget) -> Result<()> {
+        let comments = self.client.comment_list(target).await?;
+        if !self.output.is_table() {
+            output::print_items(self.output, &comments)?;
+            return Ok(());
+        }
+
+        print_thread(&comments);
+        Ok(())
+    }
+
+    pub async fn add(&self, content: &str, target: &api::CommentTarget) -> Result<()> {
+        let comment = self
+            .client
+            .comment_create(api::CommentCreate::new(
+                content.to_owned(),
+                target.to_owned(),
+            ))
+            .await?;
+        println!("{} | {}", comment.id, comment.content);
+        Ok(())
+    }
+
+    pub async fn edit(&self, id: &str, content: &str) -> Result<()> {
+        let comment = self.client.comment_update(id, content.to_owned()).await?;
+        println!("{} | {}", comment.id, comment.content);
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: &str, yes: bool) -> Result<()> {
+        let comment = self.client.comment_view(id).await?;
+
+        self.prompt
+            .confirm_destructive(&format!("Delete comment \"{}\"?", comment.content), yes)?;
+
+        self.client.comment_delete(id).await?;
+        println!("comment deleted");
+        Ok(())
+    }
+}
+
+/// Prints comments oldest first, each under its id and posting time.
+pub(super) fn print_thread(comments: &[api::Comment]) {
+    let mut comments = comments.iter().collect::<Vec<_>>();
+    comments.sort_by_key(|c| c.posted_at);
+
+    for comment in comments {
+        println!(
+            "{} {}",
+            Paint::blue(
+                comment
+                    .posted_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            ),
+            Paint::new(format!("({})", comment.id)).dimmed()
+        );
+        for line in comment.content.lines() {
+            println!("  {}", line);
+        }
+        if comment.attachment.is_some() {
+            println!("  {}", Paint::new("[attachment]").dimmed());
+        }
+    }
+}
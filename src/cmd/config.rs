@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use serde::Serialize;
+
+use crate::config;
+
+use super::output::{self, Output};
+use super::{Error, Result};
+
+/// Commands that work on the config file, they run without loading the config
+/// so a broken one can still be fixed.
+pub struct Config {
+    path: PathBuf,
+    profile: Option<String>,
+    vars: config::Vars,
+    output: Output,
+}
+
+/// A profile as `config profiles list` shows it, without its token.
+#[derive(Serialize)]
+struct Entry<'a> {
+    name: &'a str,
+    active: bool,
+    base_url: Option<&'a str>,
+    default_project: Option<&'a str>,
+    default_filter: Option<&'a str>,
+}
+
+impl Config {
+    /// `profile` is the profile selected with `--profile` or `TODOIST_PROFILE`.
+    pub fn new(
+        path: PathBuf,
+        profile: Option<String>,
+        vars: config::Vars,
+        output: Output,
+    ) -> Config {
+        Config {
+            path,
+            profile,
+            vars,
+            output,
+        }
+    }
+
+    pub fn path(&self) {
+        println!("{}", self.path.display());
+    }
+
+    pub fn get(&self, key: &str) -> Result<()> {
+        match config::get(&self.path, self.profile.as_deref(), key, &self.vars)
+            .map_err(Error::Config)?
+        {
+            Some(value) => println!("{value}"),
+            None => return Err(Error::NotFound(format!("setting `{key}`"))),
+        }
+        Ok(())
+    }
+
+    pub fn set(&self, key: &str, value: &str) -> Result<()> {
+        config::set(&self.path, self.profile.as_deref(), key, value).map_err(Error::Config)?;
+        println!("{key} set");
+        Ok(())
+    }
+
+    pub fn profiles_list(&self) -> Result<()> {
+        let profiles = config::Profiles::read(&self.path).map_err(Error::Config)?;
+        let active = self.profile.as_ref().or(profiles.active.as_ref());
+        let entries = profiles
+            .profiles
+            .iter()
+            .map(|(name, profile)| Entry {
+                name,
+                active: active == Some(name),
+                base_url: profile.base_url.as_deref(),
+                default_project: profile.default_project.as_deref(),
+                default_filter: profile.default_filter.as_deref(),
+            })
+            .collect::<Vec<_>>();
+        if !self.output.is_table() {
+            output::print_items(self.output, &entries)?;
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table
+            .set_header(vec![
+                "",
+                "Profile",
+                "Base URL",
+                "Default project",
+                "Default filter",
+            ])
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS);
+        for entry in entries {
+            table.add_row(vec![
+                if entry.active { "*" } else { "" },
+                entry.name,
+                entry.base_url.unwrap_or_default(),
+                entry.default_project.unwrap_or_default(),
+                entry.default_filter.unwrap_or_default(),
+            ]);
+        }
+        println!("{table}");
+        Ok(())
+    }
+
+    pub fn profiles_use(&self, name: &str) -> Result<()> {
+        config::use_profile(&self.path, name).map_err(Error::Config)?;
+        println!("using profile {name}");
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use std::{fmt, io};
+
+use crate::{api, oauth};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors a command can fail with, see `exit_code` for how each one ends the
+/// process.
+#[derive(Debug)]
+pub enum Error {
+    Api(api::Error),
+    OAuth(oauth::Error),
+    /// Input that could not be prompted for because the session is not
+    /// interactive, describes the argument that provides it.
+    MissingInput(String),
+    /// A name given in the arguments matches nothing, describes what was
+    /// looked for.
+    NotFound(String),
+    /// The user dismissed a prompt.
+    Cancelled,
+    /// A picker was opened without anything to pick, holds its prompt.
+    NothingToPick(String),
+    /// Reading the answer to a prompt failed.
+    Prompt(io::Error),
+    /// Writing output or local files failed.
+    Io(io::Error),
+    /// Reading or writing the config file failed.
+    Config(Box<dyn std::error::Error>),
+}
+
+impl Error {
+    /// Exit code of the process when a command fails with this error, the
+    /// codes are documented in the README.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Api(e) => e.exit_code(),
+            Error::OAuth(e) => e.exit_code(),
+            Error::MissingInput(_) | Error::NotFound(_) => 2,
+            Error::Cancelled => 130,
+            Error::NothingToPick(_) => 4,
+            Error::Prompt(_) | Error::Io(_) => 1,
+            Error::Config(_) => 10,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Api(e) => write!(f, "{e}"),
+            Error::OAuth(e) => write!(f, "{e}"),
+            Error::MissingInput(what) => {
+                write!(f, "{what} is required when prompting is not possible")
+            }
+            Error::NotFound(what) => write!(f, "{what} does not exist"),
+            Error::Cancelled => write!(f, "cancelled"),
+            Error::NothingToPick(what) => write!(f, "nothing to pick from ({what})"),
+            Error::Prompt(e) => write!(f, "failed to read input: {e}"),
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Config(e) => write!(f, "config: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Api(e) => Some(e),
+            Error::OAuth(e) => Some(e),
+            Error::Prompt(e) | Error::Io(e) => Some(e),
+            Error::Config(e) => Some(e.as_ref()),
+            Error::MissingInput(_)
+            | Error::NotFound(_)
+            | Error::Cancelled
+            | Error::NothingToPick(_) => None,
+        }
+    }
+}
+
+impl From<api::Error> for Error {
+    fn from(e: api::Error) -> Error {
+        Error::Api(e)
+    }
+}
+
+impl From<oauth::Error> for Error {
+    fn from(e: oauth::Error) -> Error {
+        Error::OAuth(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,154 @@
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+
+use crate::api;
+use crate::cache::{Kind, Store};
+
+use super::output::{self, Output};
+use super::{Prompt, Result};
+
+pub struct Labels<'a> {
+    pub(super) client: &'a api::Client,
+    pub(super) store: &'a Store,
+    pub(super) output: Output,
+    pub(super) prompt: Prompt,
+}
+
+impl Labels<'_> {
+    pub async fn list(&self, shared: bool) -> Result<()> {
+        if shared {
+            let mut names = self.client.shared_label_list().await?;
+            names.sort();
+            if !self.output.is_table() {
+                output::print_items(self.output, &names)?;
+                return Ok(());
+            }
+            for name in names {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+
+        let mut labels = self.store.labels(self.client).await?;
+        labels.sort_by_key(|l| l.order);
+        if !self.output.is_table() {
+            output::print_items(self.output, &labels)?;
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table
+            .set_header(vec!["ID", "Name", "Color", "Favorite"])
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS);
+        for label in labels {
+            table.add_row(vec![
+                label.id,
+                label.name,
+                label.color,
+                String::from(if label.is_favorite { "★" } else { "" }),
+            ]);
+        }
+        println!("{table}");
+        Ok(())
+    }
+
+    pub async fn create(&self, name: &str, color: &Option<String>, favorite: bool) -> Result<()> {
+        let mut label = api::LabelCreate::new(name.to_owned());
+        if let Some(x) = color {
+            label.color(x.to_owned());
+        }
+        if favorite {
+            label.favorite(true);
+        }
+
+        let label = self.client.label_create(label).await?;
+        self.store.invalidate(Kind::Labels);
+        println!("{} | {}", label.id, label.name);
+        Ok(())
+    }
+
+    /// Renames a personal label, or with `shared` every use of a shared label.
+    pub async fn rename(&self, label: &str, name: &str, shared: bool) -> Result<()> {
+        if shared {
+            self.client.shared_label_rename(label, name).await?;
+        } else {
+            let id = self.resolve(label).await?.id;
+            self.client
+                .label_update(
+                    &id,
+                    api::LabelUpdate::new().name(name.to_owned()).to_owned(),
+                )
+                .await?;
+        }
+        self.store.invalidate(Kind::Labels);
+        println!("label renamed");
+        Ok(())
+    }
+
+    pub async fn recolor(&self, label: &str, color: &str) -> Result<()> {
+        let id = self.resolve(label).await?.id;
+        self.client
+            .label_update(
+                &id,
+                api::LabelUpdate::new().color(color.to_owned()).to_owned(),
+            )
+            .await?;
+        self.store.invalidate(Kind::Labels);
+        println!("label recolored");
+        Ok(())
+    }
+
+    /// Flips whether the label is a favorite.
+    pub async fn favorite(&self, label: &str) -> Result<()> {
+        let label = self.resolve(label).await?;
+        let label = self
+            .client
+            .label_update(
+                &label.id,
+                api::LabelUpdate::new()
+                    .favorite(!label.is_favorite)
+                    .to_owned(),
+            )
+            .await?;
+        self.store.invalidate(Kind::Labels);
+        if label.is_favorite {
+            println!("{} added to favorites", label.name);
+        } else {
+            println!("{} removed from favorites", label.name);
+        }
+        Ok(())
+    }
+
+    /// Deletes a personal label, or with `shared` removes a shared label from all tasks.
+    pub async fn delete(&self, label: &str, shared: bool, yes: bool) -> Result<()> {
+        let (id, name) = if shared {
+            (None, label.to_owned())
+        } else {
+            let label = self.resolve(label).await?;
+            (Some(label.id), label.name)
+        };
+
+        self.prompt
+            .confirm_destructive(&format!("Remove label \"{}\" from all tasks?", name), yes)?;
+
+        match id {
+            Some(id) => self.client.label_delete(&id).await?,
+            None => self.client.shared_label_remove(&name).await?,
+        }
+        self.store.invalidate(Kind::Labels);
+        println!("label deleted");
+        Ok(())
+    }
+
+    /// Finds a personal label by id or by name.
+    async fn resolve(&self, label: &str) -> api::Result<api::Label> {
+        let labels = self.store.labels(self.client).await?;
+        match labels
+            .into_iter()
+            .find(|l| l.id == label || l.name == label)
+        {
+            Some(l) => Ok(l),
+            None => self.client.label_view(label).await,
+        }
+    }
+}
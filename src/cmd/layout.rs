@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use comfy_table::{Cell, Color};
+
+use crate::api;
+
+use super::time;
+
+/// Order of the tasks in `tasks list`, sub-tasks always stay under their parent.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Sort {
+    /// Earliest due first, tasks without a due date last
+    #[default]
+    Due,
+    /// Most urgent first
+    Priority,
+    /// By project name
+    Project,
+    /// Oldest first
+    Created,
+    /// Position of the task within its project
+    Order,
+}
+
+/// What the tasks in `tasks list` are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    Project,
+    /// Tasks with several labels show up in every group
+    Label,
+    Priority,
+    DueDate,
+    Section,
+}
+
+/// A column of the `tasks list` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Column {
+    Id,
+    Project,
+    Section,
+    Content,
+    Description,
+    Due,
+    Priority,
+    Labels,
+    Comments,
+    Created,
+    Url,
+}
+
+pub const DEFAULT_COLUMNS: [Column; 6] = [
+    Column::Id,
+    Column::Project,
+    Column::Section,
+    Column::Content,
+    Column::Due,
+    Column::Priority,
+];
+
+impl Column {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Id => "ID",
+            Column::Project => "Project",
+            Column::Section => "Section",
+            Column::Content => "Task name",
+            Column::Description => "Description",
+            Column::Due => "Due",
+            Column::Priority => "Priority",
+            Column::Labels => "Labels",
+            Column::Comments => "Comments",
+            Column::Created => "Created",
+            Column::Url => "URL",
+        }
+    }
+
+    /// Renders the column for `task`, `content` is the already indented task name.
+    pub fn cell(
+        &self,
+        task: &api::Task,
+        content: String,
+        index: &api::Index,
+        now: DateTime<Local>,
+    ) -> Cell {
+        match self {
+            Column::Id => Cell::new(&task.id),
+            Column::Project => Cell::new(index.project_name(&task.project_id)),
+            Column::Section => Cell::new(
+                task.section_id
+                    .as_deref()
+                    .map(|id| index.section_name(id))
+                    .unwrap_or_default(),
+            ),
+            Column::Content => Cell::new(content),
+            Column::Description => Cell::new(&task.description),
+            Column::Due => match &task.due {
+                Some(due) if due.is_overdue(now) => {
+                    Cell::new(time::relative(due, now)).fg(Color::Red)
+                }
+                Some(due) => Cell::new(time::relative(due, now)),
+                None => Cell::new(""),
+            },
+            Column::Priority => flag(task.priority),
+            Column::Labels => Cell::new(task.labels.join(", ")),
+            Column::Comments => Cell::new(task.comment_count),
+            Column::Created => Cell::new(
+                task.created_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M"),
+            ),
+            Column::Url => Cell::new(&task.url),
+        }
+    }
+}
+
+/// Sorts `tasks` in place, ties keep the order the API returned them in.
+pub fn sort(tasks: &mut [api::Task], sort: Sort, index: &api::Index) {
+    match sort {
+        Sort::Due => tasks.sort_by_key(|t| (t.due.is_none(), t.due.as_ref().map(|d| d.at()))),
+        Sort::Priority => tasks.sort_by_key(|t| t.priority),
+        Sort::Project => tasks.sort_by_key(|t| index.project_name(&t.project_id).to_lowercase()),
+        Sort::Created => tasks.sort_by_key(|t| t.created_at),
+        Sort::Order => tasks.sort_by_key(|t| t.order),
+    }
+}
+
+/// Splits `tasks` into named groups, keeping the order of `tasks` within a
+/// group. Groups are sorted by name, the group of tasks without a value last.
+pub fn group<'a>(
+    tasks: &'a [api::Task],
+    group_by: GroupBy,
+    index: &api::Index,
+) -> Vec<(String, Vec<&'a api::Task>)> {
+    // Keyed by (missing value, sort key) so the groups come out in order.
+    let mut groups: BTreeMap<(bool, String), (String, Vec<&api::Task>)> = BTreeMap::new();
+    for task in tasks {
+        for (key, name) in keys(task, group_by, index) {
+            groups
+                .entry(key)
+                .or_insert_with(|| (name, Vec::new()))
+                .1
+                .push(task);
+        }
+    }
+    groups.into_values().collect()
+}
+
+fn keys(task: &api::Task, group_by: GroupBy, index: &api::Index) -> Vec<((bool, String), String)> {
+    // The id keeps equally named projects and sections apart.
+    let named = |name: &str, id: &str| {
+        (
+            (false, format!("{}\0{id}", name.to_lowercase())),
+            name.to_owned(),
+        )
+    };
+    let missing = |name: &str| ((true, String::new()), name.to_owned());
+
+    match group_by {
+        GroupBy::Project => vec![named(
+            index.project_name(&task.project_id),
+            &task.project_id,
+        )],
+        GroupBy::Label if task.labels.is_empty() => vec![missing("No label")],
+        GroupBy::Label => task.labels.iter().map(|l| named(l, l)).collect(),
+        GroupBy::Priority => {
+            let priority = task.priority.to_string();
+            vec![named(&priority, &priority)]
+        }
+        GroupBy::DueDate => match &task.due {
+            Some(due) => {
+                let date = due.date.format("%Y-%m-%d").to_string();
+                vec![named(&date, &date)]
+            }
+            None => vec![missing("No date")],
+        },
+        GroupBy::Section => match &task.section_id {
+            Some(id) => vec![named(index.section_name(id), id)],
+            None => vec![missing("No section")],
+        },
+    }
+}
+
+fn flag(priority: api::Priority) -> Cell {
+    let cell = Cell::new(priority.flag_label());
+    match priority.rgb() {
+        Some((r, g, b)) => cell.fg(Color::Rgb { r, g, b }),
+        None => cell,
+    }
+}
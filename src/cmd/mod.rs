@@ -1,233 +1,926 @@
+mod auth;
+mod cache;
+mod comments;
+mod config;
+mod error;
+mod labels;
+pub mod layout;
+pub mod output;
+mod prompt;
+mod sections;
+pub mod time;
+pub mod tree;
+
+use std::collections::HashMap;
+
 use crate::api;
+use crate::cache::{Kind, Store};
+use crate::quick_add;
 
-use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
-use dialoguer::theme::ColorfulTheme;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Attribute, Cell, Table};
 use yansi::Paint;
 
+pub use self::auth::Auth;
+pub use self::cache::Cache;
+pub use self::comments::Comments;
+pub use self::config::Config;
+pub use self::error::{Error, Result};
+pub use self::labels::Labels;
+use self::layout::{Column, GroupBy, Sort};
+use self::output::Output;
+pub use self::prompt::Prompt;
+pub use self::sections::Sections;
+
 pub struct Cmd<'a> {
     pub tasks: Tasks<'a>,
     pub projects: Projects<'a>,
+    pub sections: Sections<'a>,
+    pub comments: Comments<'a>,
+    pub labels: Labels<'a>,
+    pub cache: Cache<'a>,
 }
 
-impl Cmd<'_> {
-    pub fn new(client: &api::Client) -> Cmd {
+impl<'a> Cmd<'a> {
+    pub fn new(
+        client: &'a api::Client,
+        store: &'a Store,
+        output: Output,
+        prompt: Prompt,
+    ) -> Cmd<'a> {
         Cmd {
-            tasks: Tasks { client },
-            projects: Projects { client },
+            tasks: Tasks {
+                client,
+                store,
+                output,
+                prompt,
+                default_project: None,
+                default_filter: None,
+            },
+            projects: Projects {
+                client,
+                store,
+                output,
+                prompt,
+            },
+            sections: Sections {
+                client,
+                store,
+                output,
+                prompt,
+            },
+            comments: Comments {
+                client,
+                output,
+                prompt,
+            },
+            labels: Labels {
+                client,
+                store,
+                output,
+                prompt,
+            },
+            cache: Cache {
+                client,
+                store,
+                output,
+            },
         }
     }
+
+    /// Name of the project tasks are created in when none is given.
+    pub fn with_default_project(mut self, project: Option<String>) -> Cmd<'a> {
+        self.tasks.default_project = project;
+        self
+    }
+
+    /// Filter `tasks list` uses when none is given, instead of `today|overdue`.
+    pub fn with_default_filter(mut self, filter: Option<String>) -> Cmd<'a> {
+        self.tasks.default_filter = filter;
+        self
+    }
 }
 
 pub struct Tasks<'a> {
     client: &'a api::Client,
+    store: &'a Store,
+    output: Output,
+    prompt: Prompt,
+    default_project: Option<String>,
+    default_filter: Option<String>,
 }
 
 impl Tasks<'_> {
-    pub async fn list(&self, filter: &Option<String>, raw: &Option<bool>) {
-        let resp = self
+    pub async fn list(
+        &self,
+        filter: &Option<String>,
+        sort: Sort,
+        group_by: Option<GroupBy>,
+        columns: &[Column],
+    ) -> Result<()> {
+        let mut resp = self
             .client
             .find(Some(api::TaskFilter {
-                day_filter: Some(filter.to_owned().unwrap_or(String::from("today|overdue"))),
+                day_filter: Some(
+                    filter
+                        .as_ref()
+                        .or(self.default_filter.as_ref())
+                        .map_or(String::from("today|overdue"), String::to_owned),
+                ),
+                ..Default::default()
             }))
-            .await;
-        match resp {
-            Ok(resp) => {
-                let mut output_rows: Vec<Vec<String>> = Vec::new();
-
-                for task in resp.iter() {
-                    let project_name = task.project(&self.client).await.unwrap().name;
-                    output_rows.push(vec![
-                        task.id.to_owned(),
-                        project_name,
-                        task.content.to_owned(),
-                        task.priority.to_string(),
-                    ]);
-                }
+            .await?;
 
-                if raw.unwrap_or(false) {
-                    for row in output_rows {
-                        for field in row {
-                            print!("{},", field);
-                        }
-                        println!();
-                    }
-                } else {
-                    let mut table = Table::new();
-                    table
-                        .set_header(vec!["ID", "Project", "Task name", "Priority"])
-                        .load_preset(UTF8_FULL)
-                        .apply_modifier(UTF8_ROUND_CORNERS);
-
-                    table.add_rows(output_rows);
-                    println!("{table}");
-                }
+        let index = self.store.index(self.client).await?;
+        layout::sort(&mut resp, sort, &index);
+
+        if !self.output.is_table() {
+            output::print_items(self.output, &resp)?;
+            return Ok(());
+        }
+
+        let groups = match group_by {
+            Some(group_by) => layout::group(&resp, group_by, &index),
+            None => vec![(String::new(), resp.iter().collect())],
+        };
+
+        let now = chrono::Local::now();
+        let mut table = Table::new();
+        table
+            .set_header(columns.iter().map(|c| c.title()))
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS);
+
+        for (name, tasks) in groups {
+            if group_by.is_some() {
+                let mut header =
+                    vec![Cell::new(format!("{} ({})", name, tasks.len()))
+                        .add_attribute(Attribute::Bold)];
+                header.resize_with(columns.len(), || Cell::new(""));
+                table.add_row(header);
             }
-            Err(e) => {
-                println!("{}", e);
+
+            let tasks = tasks.into_iter().cloned().collect::<Vec<_>>();
+            for (depth, task) in task_tree(&tasks) {
+                table.add_row(
+                    columns
+                        .iter()
+                        .map(|c| c.cell(task, indent(depth, &task.content), &index, now))
+                        .collect::<Vec<_>>(),
+                );
             }
         }
+
+        println!("{table}");
+        Ok(())
     }
 
+    /// Creates a task, prompting for everything that was not given. With
+    /// `parent` set to `Some(None)` the parent task is picked interactively.
+    /// Without a terminal optional fields are left unset.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self,
         content: &Option<String>,
         due: &Option<String>,
         project: &Option<String>,
+        section: &Option<String>,
+        parent: &Option<Option<String>>,
         labels: &Vec<String>,
-        priority: &Option<u8>,
-    ) {
-        let theme = ColorfulTheme::default();
-        let prompt = |prompt: &str| -> String {
-            dialoguer::Input::with_theme(&theme)
-                .with_prompt(prompt)
-                .interact()
-                .expect("failed to get input from promt")
-        };
-
-        let task_create = api::TaskCreate::new(if let Some(x) = content {
-            x.to_owned()
-        } else {
-            prompt("Your tasks name")
-        })
-        .project(if let Some(x) = project {
-            x.to_owned()
-        } else {
-            let selections = self
-                .client
-                .project_list()
-                .await
-                .expect("failed to fetch projects");
-
-            let selected_project = dialoguer::FuzzySelect::with_theme(&theme)
-                .with_prompt("Project:")
-                .items(&selections)
-                .default(0)
-                .interact()
-                .unwrap();
-
-            selections.get(selected_project).unwrap().id.to_owned()
-        })
-        .due(if let Some(x) = due {
-            x.to_owned()
-        } else {
-            prompt("Due date")
-        })
-        .labels(if labels.len() > 0 {
-            labels.to_owned()
-        } else {
-            let items = self.client.label_list().await.unwrap();
+        priority: &Option<api::Priority>,
+    ) -> Result<()> {
+        let mut task_create = api::TaskCreate::new(match content {
+            Some(x) => x.to_owned(),
+            None => self.prompt.input("Your tasks name", "the task content")?,
+        });
 
-            let selected_labels = dialoguer::MultiSelect::new()
-                .with_prompt("Lables:")
-                .items(
-                    &items
-                        .iter()
-                        .map(|x| -> String { x.name.to_owned() })
-                        .collect::<Vec<String>>(),
+        let parent_id = match parent {
+            Some(Some(x)) => Some(x.to_owned()),
+            Some(None) => Some(
+                self.pick_task(
+                    api::TaskFilter {
+                        project_id: project.to_owned(),
+                        ..Default::default()
+                    },
+                    "a --parent id",
                 )
-                .interact()
-                .unwrap();
+                .await?
+                .id,
+            ),
+            None => None,
+        };
 
-            let mut labels: Vec<String> = Vec::new();
+        // Sub-tasks and tasks in a section end up in the project of their
+        // parent or section, so only use the default or ask for a project if
+        // neither is known. Without one the task goes to the inbox.
+        if let Some(x) = project {
+            task_create.project(x.to_owned());
+        } else if let Some(x) = section {
+            task_create.project(self.client.section_view(x).await?.project_id);
+        } else if let (None, Some(name)) = (&parent_id, &self.default_project) {
+            let projects = self.store.projects(self.client).await?;
+            let project = quick_add::find_project(&projects, Some(name))
+                .ok_or_else(|| Error::NotFound(format!("default project \"{name}\"")))?;
+            task_create.project(project.id.to_owned());
+        } else if parent_id.is_none() && self.prompt.is_interactive() {
+            task_create.project(
+                pick_project(self.client, self.store, &self.prompt, "--project")
+                    .await?
+                    .id,
+            );
+        }
+
+        let due = match due {
+            Some(x) => Some(x.to_owned()),
+            None => self.prompt.optional_input("Due date")?,
+        };
+        if let Some(x) = due {
+            task_create.due(x);
+        }
 
-            for i in selected_labels {
-                labels.push(items[i].name.to_owned());
+        if !labels.is_empty() {
+            task_create.labels(labels.to_owned());
+        } else if self.prompt.is_interactive() {
+            let names = self
+                .store
+                .labels(self.client)
+                .await?
+                .into_iter()
+                .map(|x| x.name)
+                .collect::<Vec<String>>();
+            if let Some(selected) = self.prompt.multi_select("Lables:", &names, &[])? {
+                task_create.labels(selected.into_iter().map(|i| names[i].to_owned()).collect());
             }
-            labels
-        })
-        .priority(if let Some(x) = priority {
-            x.to_owned()
-        } else {
-            prompt("Priority").parse().unwrap()
-        })
-        .to_owned();
+        }
 
-        let new_task = self.client.create(task_create).await.unwrap();
+        let priority = match priority {
+            Some(x) => Some(*x),
+            None => self.prompt.optional_input("Priority")?,
+        };
+        if let Some(x) = priority {
+            task_create.priority(x);
+        }
+        if let Some(x) = section {
+            task_create.section(x.to_owned());
+        }
+        if let Some(x) = parent_id {
+            task_create.parent(x);
+        }
+
+        let new_task = self.client.create(task_create).await?;
         println!("{}", new_task.content);
+        Ok(())
     }
 
-    pub async fn done(&self, id: &Option<String>) {
-        if let Some(x) = id {
-            match self.client.close(x).await {
-                Ok(_) => println!("task done"),
-                Err(e) => {
-                    println!("{}", e);
-                }
+    /// Creates a task from Todoist's quick add syntax. With `dry_run` only
+    /// prints how the text is read locally, with `local` the text is read
+    /// locally instead of by the quick add endpoint.
+    pub async fn quick_add(&self, text: &str, dry_run: bool, local: bool) -> Result<()> {
+        if dry_run {
+            let quick_add = quick_add::parse(text);
+            if !self.output.is_table() {
+                output::print_item(self.output, &quick_add)?;
+                return Ok(());
             }
-        } else {
-            let theme = ColorfulTheme::default();
-            let selections = self
-                .client
-                .find(Some(api::TaskFilter {
-                    day_filter: Some(String::from("today")),
-                }))
-                .await
-                .expect("failed to fetch tasks");
-
-            let selected_task = dialoguer::FuzzySelect::with_theme(&theme)
-                .with_prompt("Task:")
-                .items(
-                    &selections
-                        .iter()
-                        .map(|x| -> String { x.content.to_owned() })
-                        .collect::<Vec<String>>(),
-                )
-                .default(0)
-                .interact()
-                .unwrap();
 
-            self.client
-                .close(&selections[selected_task].id)
-                .await
-                .unwrap();
+            println!("Task : {}", Paint::green(&quick_add.content));
+            println!(
+                "Project : {}",
+                Paint::green(quick_add.project.as_deref().unwrap_or("Inbox"))
+            );
+            if let Some(section) = &quick_add.section {
+                println!("Section : {}", Paint::green(section));
+            }
+            print!("Labels : ");
+            for label in &quick_add.labels {
+                print!("{} ", Paint::magenta(label))
+            }
+            println!();
+            println!(
+                "Priority : {}",
+                flag(quick_add.priority.unwrap_or_default())
+            );
+            if let Some(due) = &quick_add.due {
+                println!("Due date : {}", Paint::green(due));
+            }
+            return Ok(());
+        }
+
+        if local {
+            let mut quick_add = quick_add::parse(text);
+            if quick_add.project.is_none() {
+                quick_add.project = self.default_project.to_owned();
+            }
+            let task_create = self.resolve(&quick_add).await?;
+            let task = self.client.create(task_create).await?;
+            if !self.output.is_table() {
+                output::print_item(self.output, &task)?;
+                return Ok(());
+            }
+            println!("{} | {}", task.id, task.content);
+            return Ok(());
         }
+
+        let item = self.client.quick_add(text).await?;
+        // Quick add creates labels that do not exist yet.
+        if !item.labels.is_empty() {
+            self.store.invalidate(Kind::Labels);
+        }
+        if !self.output.is_table() {
+            output::print_item(self.output, &item)?;
+            return Ok(());
+        }
+        println!("{} | {}", item.id, item.content);
+        Ok(())
     }
 
-    pub async fn view(&self, id: &String) {
-        match self.client.view(id.to_string()).await {
-            Ok(task) => {
-                let project = self.client.project_view(task.project_id).await.unwrap();
+    /// Turns the parsed text into a task, offering to create the projects,
+    /// sections and labels it names that do not exist yet.
+    async fn resolve(&self, quick_add: &quick_add::QuickAdd) -> Result<api::TaskCreate> {
+        let mut projects = self.store.projects(self.client).await?;
+        let mut sections = self.store.sections(self.client).await?;
+        let mut labels = self.store.labels(self.client).await?;
+        let unknown = match quick_add.resolve(&projects, &sections, &labels) {
+            Ok(task_create) => return Ok(task_create),
+            Err(unknown) => unknown,
+        };
+
+        for missing in &unknown {
+            match self.prompt.confirm(&format!("Create {missing}?"), true)? {
+                Some(true) => {}
+                Some(false) => return Err(Error::Cancelled),
+                None => return Err(Error::NotFound(missing.to_string())),
+            }
+        }
 
-                println!("Task : {}", Paint::green(task.content));
-                if task.due.is_some() {
-                    println!("Due date : {}", Paint::red(task.due.unwrap().date));
+        // Projects come first in `unknown`, so sections can be put into
+        // projects created just before them.
+        for missing in unknown {
+            match missing {
+                quick_add::Unknown::Project(name) => {
+                    projects.push(
+                        self.client
+                            .project_create(api::ProjectCreate::new(name))
+                            .await?,
+                    );
+                    self.store.invalidate(Kind::Projects);
                 }
-                println!("Priority : {}", Paint::green(task.priority));
-                println!("Project : {}", Paint::green(project.name));
-                print!("Labels : ");
-                for lable in task.labels {
-                    print!("{} ", Paint::magenta(lable))
+                quick_add::Unknown::Section { project, name } => {
+                    let project = quick_add::find_project(&projects, project.as_deref())
+                        .ok_or_else(|| Error::NotFound(String::from("the inbox project")))?;
+                    sections.push(
+                        self.client
+                            .section_create(api::SectionCreate::new(name, project.id.to_owned()))
+                            .await?,
+                    );
+                    self.store.invalidate(Kind::Sections);
                 }
-                println!();
+                quick_add::Unknown::Label(name) => {
+                    labels.push(
+                        self.client
+                            .label_create(api::LabelCreate::new(name))
+                            .await?,
+                    );
+                    self.store.invalidate(Kind::Labels);
+                }
+            }
+        }
+
+        quick_add
+            .resolve(&projects, &sections, &labels)
+            .map_err(|unknown| Error::NotFound(unknown[0].to_string()))
+    }
+
+    /// Applies `update` to the task, or opens a form pre-filled with the
+    /// current values if the update is empty.
+    pub async fn edit(&self, id: &str, update: &api::TaskUpdate) -> Result<()> {
+        let update = if update.is_empty() {
+            self.edit_form(self.client.view(id.to_string()).await?)
+                .await?
+        } else {
+            update.to_owned()
+        };
+
+        if update.is_empty() {
+            println!("nothing to update");
+            return Ok(());
+        }
+
+        let task = self.client.update(id, update).await?;
+        println!("{}", task.content);
+        Ok(())
+    }
+
+    async fn edit_form(&self, task: api::Task) -> Result<api::TaskUpdate> {
+        self.prompt.require("a flag with the changes")?;
+        let mut update = api::TaskUpdate::new();
+
+        let content = self.prompt.edit("Your tasks name", &task.content)?;
+        if content != task.content {
+            update.content(content);
+        }
+
+        let description = self.prompt.edit("Description", &task.description)?;
+        if description != task.description {
+            update.description(description);
+        }
+
+        let current_due = task.due.map(|d| d.string).unwrap_or_default();
+        let due = self.prompt.edit("Due date", &current_due)?;
+        if due != current_due {
+            update.due(if due.is_empty() {
+                String::from("no date")
+            } else {
+                due
+            });
+        }
+
+        let priority = self.prompt.edit("Priority", &task.priority)?;
+        if priority != task.priority {
+            update.priority(priority);
+        }
+
+        let names = self
+            .store
+            .labels(self.client)
+            .await?
+            .into_iter()
+            .map(|x| x.name)
+            .collect::<Vec<String>>();
+        let defaults = names
+            .iter()
+            .map(|x| task.labels.contains(x))
+            .collect::<Vec<bool>>();
+        if let Some(selected) = self.prompt.multi_select("Lables:", &names, &defaults)? {
+            let labels: Vec<String> = selected.into_iter().map(|i| names[i].to_owned()).collect();
+            if labels != task.labels {
+                update.labels(labels);
             }
-            Err(e) => println!("Failed to view the task: {}", e),
+        }
+
+        Ok(update)
+    }
+
+    pub async fn done(&self, id: &Option<String>) -> Result<()> {
+        if let Some(x) = id {
+            self.client.close(x).await?;
+            println!("task done");
+        } else {
+            let task = self.pick_task(today(), "the task id").await?;
+            self.client.close(&task.id).await?;
+            println!("task \"{}\" done", task.content);
+        }
+        Ok(())
+    }
+
+    /// Reopens the task, or lets the user pick one of the recently
+    /// completed tasks if `id` is `None`.
+    pub async fn reopen(&self, id: &Option<String>) -> Result<()> {
+        if let Some(x) = id {
+            self.client.reopen(x).await?;
+            println!("task reopened");
+            return Ok(());
+        }
+
+        self.prompt.require("the task id")?;
+        let mut completed = self.client.completed_list().await?;
+        let selected = self.prompt.pick(
+            "Completed task:",
+            &completed
+                .iter()
+                .map(|x| x.content.to_owned())
+                .collect::<Vec<String>>(),
+            "the task id",
+        )?;
+        let task = completed.swap_remove(selected);
+        self.client.reopen(&task.task_id).await?;
+        println!("task \"{}\" reopened", task.content);
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: &Option<String>, yes: bool) -> Result<()> {
+        let task = if let Some(x) = id {
+            self.client.view(x.to_owned()).await?
+        } else {
+            self.pick_task(today(), "the task id").await?
         };
+
+        self.prompt
+            .confirm_destructive(&format!("Delete task \"{}\"?", task.content), yes)?;
+
+        self.client.delete(&task.id).await?;
+        println!("task deleted");
+        Ok(())
+    }
+
+    /// Lets the user fuzzy search through the tasks matching `filter`, `what`
+    /// names the argument to give instead when prompting is not possible.
+    async fn pick_task(&self, filter: api::TaskFilter, what: &str) -> Result<api::Task> {
+        self.prompt.require(what)?;
+        let mut selections = self.client.find(Some(filter)).await?;
+
+        let selected_task = self.prompt.pick(
+            "Task:",
+            &selections
+                .iter()
+                .map(|x| -> String { x.content.to_owned() })
+                .collect::<Vec<String>>(),
+            what,
+        )?;
+
+        Ok(selections.swap_remove(selected_task))
+    }
+
+    pub async fn view(&self, id: &str) -> Result<()> {
+        let task = self.client.view(id.to_string()).await?;
+        if !self.output.is_table() {
+            output::print_item(self.output, &task)?;
+            return Ok(());
+        }
+        let project = self.client.project_view(task.project_id.to_owned()).await?;
+        let project_tasks = self
+            .client
+            .find(Some(api::TaskFilter {
+                project_id: Some(task.project_id.to_owned()),
+                ..Default::default()
+            }))
+            .await?;
+
+        println!("Task : {}", Paint::green(&task.content));
+        if let Some(due) = &task.due {
+            let now = chrono::Local::now();
+            let text = format!("{} ({})", time::date(due), time::relative(due, now));
+            if due.is_overdue(now) {
+                println!("Due date : {}", Paint::red(text));
+            } else {
+                println!("Due date : {}", Paint::green(text));
+            }
+        }
+        println!("Priority : {}", flag(task.priority));
+        println!("Project : {}", Paint::green(project.name));
+        print!("Labels : ");
+        for lable in &task.labels {
+            print!("{} ", Paint::magenta(lable))
+        }
+        println!();
+
+        let subtasks = subtasks(&project_tasks, &task.id);
+        if !subtasks.is_empty() {
+            println!();
+            println!("Subtasks :");
+            for (depth, subtask) in subtasks {
+                println!(
+                    "  {}  {}",
+                    indent(depth, &subtask.content),
+                    Paint::new(&subtask.id).dimmed()
+                );
+            }
+        }
+
+        if task.comment_count > 0 {
+            let comments = self
+                .client
+                .comment_list(&api::CommentTarget::Task(task.id))
+                .await?;
+            println!();
+            println!("Comments :");
+            comments::print_thread(&comments);
+        }
+        Ok(())
+    }
+}
+
+fn today() -> api::TaskFilter {
+    api::TaskFilter {
+        day_filter: Some(String::from("today")),
+        ..Default::default()
+    }
+}
+
+/// Nests sub-tasks under their parent, keeping the order of `tasks` otherwise.
+fn task_tree(tasks: &[api::Task]) -> Vec<(usize, &api::Task)> {
+    let position: HashMap<&str, i64> = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.id.as_str(), i as i64))
+        .collect();
+    tree::flatten(
+        tasks,
+        |t| &t.id,
+        |t| t.parent_id.as_deref(),
+        |t| position[t.id.as_str()],
+    )
+}
+
+/// Returns all descendants of the task `id`, depth relative to that task.
+fn subtasks<'a>(tasks: &'a [api::Task], id: &str) -> Vec<(usize, &'a api::Task)> {
+    let tree = task_tree(tasks);
+    let Some(start) = tree.iter().position(|(_, t)| t.id == id) else {
+        return Vec::new();
+    };
+    let root_depth = tree[start].0;
+    tree[start + 1..]
+        .iter()
+        .take_while(|(depth, _)| *depth > root_depth)
+        .map(|(depth, t)| (depth - root_depth - 1, *t))
+        .collect()
+}
+
+fn flag(priority: api::Priority) -> Paint<String> {
+    let flag = priority.flag_label();
+    match priority.rgb() {
+        Some((r, g, b)) => Paint::rgb(r, g, b, flag),
+        None => Paint::new(flag),
     }
 }
 
+fn indent(depth: usize, content: &str) -> String {
+    if depth == 0 {
+        content.to_owned()
+    } else {
+        format!("{}└ {}", "  ".repeat(depth - 1), content)
+    }
+}
+
+/// Lets the user fuzzy search through all projects, `what` names the
+/// argument to give instead when prompting is not possible.
+async fn pick_project(
+    client: &api::Client,
+    store: &Store,
+    prompt: &Prompt,
+    what: &str,
+) -> Result<api::Project> {
+    prompt.require(what)?;
+    let mut selections = store.projects(client).await?;
+
+    let selected_project = prompt.pick("Project:", &selections, what)?;
+
+    Ok(selections.swap_remove(selected_project))
+}
+
 pub struct Projects<'a> {
     client: &'a api::Client,
+    store: &'a Store,
+    output: Output,
+    prompt: Prompt,
 }
 
 impl Projects<'_> {
-    pub async fn list(&self) {
-        match self.client.project_list().await {
-            Ok(projects) => {
-                for project in projects.iter() {
-                    println!("{} | {}", project.id, project.name)
-                }
+    /// Prints projects as a tree, optionally with the number of open tasks in each.
+    pub async fn list(&self, counts: bool) -> Result<()> {
+        let projects = self.store.projects(self.client).await?;
+        let tree = tree::flatten(
+            &projects,
+            |p| &p.id,
+            |p| p.parent_id.as_deref(),
+            |p| p.order,
+        );
+
+        if !self.output.is_table() {
+            let ordered = tree.into_iter().map(|(_, p)| p).collect::<Vec<_>>();
+            output::print_items(self.output, &ordered)?;
+            return Ok(());
+        }
+
+        let mut task_counts: HashMap<String, usize> = HashMap::new();
+        if counts {
+            for task in self.client.find(None).await? {
+                *task_counts.entry(task.project_id).or_default() += 1;
             }
-            Err(e) => {
-                println!("{}", e);
+        }
+
+        for (depth, project) in tree {
+            let bullet = match api::rgb(&project.color) {
+                Some((r, g, b)) => Paint::rgb(r, g, b, "●"),
+                None => Paint::new("●"),
+            };
+            let mut line = format!("{}{} {}", "  ".repeat(depth), bullet, project.name);
+            if project.is_favorite {
+                line += &format!(" {}", Paint::yellow("★"));
             }
+            if project.is_inbox_project {
+                line += &format!(" {}", Paint::cyan("[inbox]"));
+            }
+            if project.is_team_inbox {
+                line += &format!(" {}", Paint::cyan("[team inbox]"));
+            }
+            if project.is_shared {
+                line += &format!(" {}", Paint::magenta("[shared]"));
+            }
+            if counts {
+                let count = task_counts.get(&project.id).copied().unwrap_or_default();
+                line += &format!(" ({})", count);
+            }
+            println!("{}  {}", line, Paint::new(&project.id).dimmed());
+        }
+        Ok(())
+    }
+
+    pub async fn view(&self, id: &str) -> Result<()> {
+        let project = self.client.project_view(id.to_string()).await?;
+        if !self.output.is_table() {
+            output::print_item(self.output, &project)?;
+            return Ok(());
+        }
+        println!("{} | {}", project.id, project.name);
+        Ok(())
+    }
+
+    pub async fn create(
+        &self,
+        name: &Option<String>,
+        color: &Option<String>,
+        parent: &Option<String>,
+        favorite: &Option<bool>,
+        view_style: &Option<String>,
+    ) -> Result<()> {
+        let select = |prompt: &str, items: &[&str]| -> Result<Option<String>> {
+            Ok(self
+                .prompt
+                .select(prompt, items, 0)?
+                .map(|i| items[i].to_owned()))
         };
+
+        let mut project_create = api::ProjectCreate::new(match name {
+            Some(x) => x.to_owned(),
+            None => self
+                .prompt
+                .input("Your projects name", "the project name")?,
+        });
+
+        let color = match color {
+            Some(x) => Some(x.to_owned()),
+            None => select("Color:", &api::COLORS)?,
+        };
+        if let Some(x) = color {
+            project_create.color(x);
+        }
+        let favorite = match favorite {
+            Some(x) => Some(*x),
+            None => self.prompt.confirm("Favorite?", false)?,
+        };
+        if let Some(x) = favorite {
+            project_create.favorite(x);
+        }
+        let view_style = match view_style {
+            Some(x) => Some(x.to_owned()),
+            None => select("View style:", &api::VIEW_STYLES)?,
+        };
+        if let Some(x) = view_style {
+            project_create.view_style(x);
+        }
+
+        if let Some(x) = parent {
+            project_create.parent(x.to_owned());
+        } else if self.prompt.is_interactive() {
+            let mut selections = self.store.projects(self.client).await?;
+            let mut items = vec![String::from("(no parent)")];
+            items.extend(selections.iter().map(|p| p.name.to_owned()));
+
+            let selected = self.prompt.pick("Parent project:", &items, "--parent")?;
+            if selected > 0 {
+                project_create.parent(selections.swap_remove(selected - 1).id);
+            }
+        }
+
+        let project = self.client.project_create(project_create).await?;
+        self.store.invalidate(Kind::Projects);
+        println!("{} | {}", project.id, project.name);
+        Ok(())
+    }
+
+    /// Applies `update` and moves the project under `parent`, `Some(None)`
+    /// being the top level. Opens a form pre-filled with the current values
+    /// if neither changes anything.
+    pub async fn edit(
+        &self,
+        id: &Option<String>,
+        update: &api::ProjectUpdate,
+        parent: &Option<Option<String>>,
+    ) -> Result<()> {
+        let mut project = match id {
+            Some(x) => self.client.project_view(x.to_owned()).await?,
+            None => pick_project(self.client, self.store, &self.prompt, "the project id").await?,
+        };
+
+        let (update, parent) = if update.is_empty() && parent.is_none() {
+            self.edit_form(&project).await?
+        } else {
+            (update.to_owned(), parent.to_owned())
+        };
+
+        if update.is_empty() && parent.is_none() {
+            println!("nothing to update");
+            return Ok(());
+        }
+
+        if let Some(parent) = parent {
+            self.client
+                .project_move(&project.id, parent.as_deref())
+                .await?;
+            self.store.invalidate(Kind::Projects);
+        }
+        if !update.is_empty() {
+            project = self.client.project_update(&project.id, update).await?;
+            self.store.invalidate(Kind::Projects);
+        }
+        println!("{} | {}", project.id, project.name);
+        Ok(())
     }
-    pub async fn view(&self, id: &String) {
-        match self.client.project_view(id.to_string()).await {
-            Ok(project) => {
-                println!("{} | {}", project.id, project.name);
+
+    async fn edit_form(
+        &self,
+        project: &api::Project,
+    ) -> Result<(api::ProjectUpdate, Option<Option<String>>)> {
+        self.prompt.require("a flag with the changes")?;
+        let select = |prompt: &str, items: &[&str], current: &str| -> Result<String> {
+            let default = items.iter().position(|x| *x == current).unwrap_or(0);
+            Ok(self
+                .prompt
+                .select(prompt, items, default)?
+                .map(|i| items[i].to_owned())
+                .unwrap_or_else(|| current.to_owned()))
+        };
+
+        let mut update = api::ProjectUpdate::new();
+
+        let name = self.prompt.edit("Your projects name", &project.name)?;
+        if name != project.name {
+            update.name(name);
+        }
+
+        let color = select("Color:", &api::COLORS, &project.color)?;
+        if color != project.color {
+            update.color(color);
+        }
+
+        let favorite = self
+            .prompt
+            .confirm("Favorite?", project.is_favorite)?
+            .unwrap_or(project.is_favorite);
+        if favorite != project.is_favorite {
+            update.favorite(favorite);
+        }
+
+        let view_style = select("View style:", &api::VIEW_STYLES, &project.view_style)?;
+        if view_style != project.view_style {
+            update.view_style(view_style);
+        }
+
+        let mut parents = self.store.projects(self.client).await?;
+        parents.retain(|p| p.id != project.id);
+        let mut items = vec![String::from("(no parent)")];
+        items.extend(parents.iter().map(|p| p.name.to_owned()));
+        let current = parents
+            .iter()
+            .position(|p| Some(&p.id) == project.parent_id.as_ref())
+            .map_or(0, |i| i + 1);
+        let parent = self
+            .prompt
+            .select("Parent project:", &items, current)?
+            .filter(|i| *i != current)
+            .map(|i| (i > 0).then(|| parents.swap_remove(i - 1).id));
+
+        Ok((update, parent))
+    }
+
+    pub async fn archive(&self, id: &Option<String>) -> Result<()> {
+        let id = match id {
+            Some(x) => x.to_owned(),
+            None => {
+                pick_project(self.client, self.store, &self.prompt, "the project id")
+                    .await?
+                    .id
             }
-            Err(e) => println!("{}", e),
         };
+
+        self.client.project_archive(&id).await?;
+        self.store.invalidate(Kind::Projects);
+        println!("project archived");
+        Ok(())
+    }
+
+    pub async fn unarchive(&self, id: &str) -> Result<()> {
+        self.client.project_unarchive(id).await?;
+        self.store.invalidate(Kind::Projects);
+        println!("project unarchived");
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: &Option<String>, yes: bool) -> Result<()> {
+        let project = match id {
+            Some(x) => self.client.project_view(x.to_owned()).await?,
+            None => pick_project(self.client, self.store, &self.prompt, "the project id").await?,
+        };
+
+        self.prompt.confirm_destructive(
+            &format!("Delete project \"{}\" with all its tasks?", project.name),
+            yes,
+        )?;
+
+        self.client.project_delete(&project.id).await?;
+        self.store.invalidate(Kind::Projects);
+        self.store.invalidate(Kind::Sections);
+        println!("project deleted");
+        Ok(())
     }
 }
@@ -0,0 +1,173 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Format the listing and viewing commands print their results in.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// Human readable output
+    #[default]
+    Table,
+    /// A single JSON document
+    Json,
+    /// One JSON document per line
+    Ndjson,
+    /// Comma separated values with a header row
+    Csv,
+    /// Tab separated values with a header row
+    Tsv,
+    /// A single YAML document
+    Yaml,
+}
+
+impl Output {
+    pub fn is_table(&self) -> bool {
+        *self == Output::Table
+    }
+}
+
+/// Prints `items` to stdout in the machine readable `format`.
+pub fn print_items<T: Serialize>(format: Output, items: &[T]) -> io::Result<()> {
+    write_items(format, items, &mut io::stdout().lock())
+}
+
+/// Prints a single `item` to stdout in the machine readable `format`.
+pub fn print_item<T: Serialize>(format: Output, item: &T) -> io::Result<()> {
+    write_item(format, item, &mut io::stdout().lock())
+}
+
+pub fn write_items<T: Serialize, W: Write>(
+    format: Output,
+    items: &[T],
+    w: &mut W,
+) -> io::Result<()> {
+    let values = items
+        .iter()
+        .map(to_value)
+        .collect::<io::Result<Vec<Value>>>()?;
+    match format {
+        Output::Json | Output::Table => writeln!(w, "{}", pretty_json(&values)?),
+        Output::Yaml => write!(w, "{}", yaml(&values)?),
+        Output::Ndjson | Output::Csv | Output::Tsv => write_rows(format, &values, w),
+    }
+}
+
+pub fn write_item<T: Serialize, W: Write>(format: Output, item: &T, w: &mut W) -> io::Result<()> {
+    let value = to_value(item)?;
+    match format {
+        Output::Json | Output::Table => writeln!(w, "{}", pretty_json(&value)?),
+        Output::Yaml => write!(w, "{}", yaml(&value)?),
+        Output::Ndjson | Output::Csv | Output::Tsv => write_rows(format, &[value], w),
+    }
+}
+
+fn to_value<T: Serialize>(item: &T) -> io::Result<Value> {
+    Ok(serde_json::to_value(item)?)
+}
+
+fn pretty_json(value: &impl Serialize) -> io::Result<String> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+fn yaml(value: &impl Serialize) -> io::Result<String> {
+    serde_yaml::to_string(value).map_err(io::Error::other)
+}
+
+fn write_rows<W: Write>(format: Output, values: &[Value], w: &mut W) -> io::Result<()> {
+    if format == Output::Ndjson {
+        for value in values {
+            writeln!(w, "{}", value)?;
+        }
+        return Ok(());
+    }
+
+    let rows = values
+        .iter()
+        .map(flatten)
+        .collect::<Vec<Map<String, Value>>>();
+    let header = header(&rows);
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(if format == Output::Tsv { b'\t' } else { b',' })
+        .from_writer(w);
+    writer.write_record(&header)?;
+    for row in rows {
+        writer.write_record(header.iter().map(|key| match row.get(key) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.to_owned(),
+            Some(v) => v.to_string(),
+        }))?;
+    }
+    writer.flush()
+}
+
+/// Flattens nested objects into dotted keys (`due.date`) and joins arrays of
+/// scalars with commas, so every field fits in a single cell.
+fn flatten(value: &Value) -> Map<String, Value> {
+    let mut row = Map::new();
+    match value {
+        Value::Object(fields) => flatten_into(&mut row, None, fields),
+        v => {
+            row.insert(String::from("value"), v.to_owned());
+        }
+    }
+    row
+}
+
+fn flatten_into(row: &mut Map<String, Value>, prefix: Option<&str>, fields: &Map<String, Value>) {
+    for (key, value) in fields {
+        let key = match prefix {
+            Some(p) => format!("{p}.{key}"),
+            None => key.to_owned(),
+        };
+        match value {
+            Value::Object(nested) => flatten_into(row, Some(&key), nested),
+            Value::Array(items) => {
+                let joined = items
+                    .iter()
+                    .map(|v| match v {
+                        Value::String(s) => s.to_owned(),
+                        v => v.to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",");
+                row.insert(key, Value::String(joined));
+            }
+            v => {
+                row.insert(key, v.to_owned());
+            }
+        }
+    }
+}
+
+/// Collects the keys of all rows in the order they first appear. A key that
+/// was null in one row but an object in another (`due` vs `due.date`) only
+/// keeps the nested columns.
+fn header(rows: &[Map<String, Value>]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for row in rows {
+        for key in row.keys() {
+            if keys.contains(key) {
+                continue;
+            }
+            let root = key.split('.').next().unwrap_or(key);
+            let root_prefix = format!("{root}.");
+            match keys
+                .iter()
+                .rposition(|k| k == root || k.starts_with(&root_prefix))
+            {
+                Some(pos) => keys.insert(pos + 1, key.to_owned()),
+                None => keys.push(key.to_owned()),
+            }
+        }
+    }
+
+    let nested = keys.clone();
+    keys.retain(|key| {
+        let prefix = format!("{key}.");
+        !nested.iter().any(|k| k.starts_with(&prefix))
+    });
+    keys
+}
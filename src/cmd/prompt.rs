@@ -0,0 +1,190 @@
+use std::fmt::Display;
+use std::io;
+use std::str::FromStr;
+
+use dialoguer::theme::ColorfulTheme;
+
+use super::error::{Error, Result};
+
+/// Asks for input the command line did not provide. When prompting is not
+/// possible, optional input is left unset and required input fails with
+/// `Error::MissingInput`.
+#[derive(Debug, Clone, Copy)]
+pub struct Prompt {
+    interactive: bool,
+}
+
+impl Prompt {
+    pub fn new(interactive: bool) -> Prompt {
+        Prompt { interactive }
+    }
+
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    /// Fails unless prompting is possible, `what` names the argument that
+    /// has to be given instead.
+    pub fn require(&self, what: &str) -> Result<()> {
+        if self.interactive {
+            Ok(())
+        } else {
+            Err(Error::MissingInput(what.to_owned()))
+        }
+    }
+
+    /// Asks for a value that can not be left out.
+    pub fn input<T>(&self, prompt: &str, what: &str) -> Result<T>
+    where
+        T: Clone + ToString + FromStr,
+        T::Err: Display + std::fmt::Debug,
+    {
+        self.require(what)?;
+        dialoguer::Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .interact_text()
+            .map_err(failed)
+    }
+
+    /// Asks for a secret without showing what is typed.
+    pub fn password(&self, prompt: &str, what: &str) -> Result<String> {
+        self.require(what)?;
+        dialoguer::Password::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .interact()
+            .map_err(failed)
+    }
+
+    /// Asks for a value that may be left out by giving an empty answer.
+    pub fn optional_input<T>(&self, prompt: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        if !self.interactive {
+            return Ok(None);
+        }
+        let answer: String = dialoguer::Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .validate_with(|answer: &String| -> std::result::Result<(), String> {
+                if answer.is_empty() {
+                    return Ok(());
+                }
+                answer.parse::<T>().map(|_| ()).map_err(|e| e.to_string())
+            })
+            .interact_text()
+            .map_err(failed)?;
+        Ok(answer.parse().ok())
+    }
+
+    /// Asks for a new value of a field, starting from its `current` value.
+    pub fn edit<T>(&self, prompt: &str, current: &T) -> Result<T>
+    where
+        T: Clone + ToString + FromStr,
+        T::Err: Display + std::fmt::Debug,
+    {
+        self.require(prompt)?;
+        dialoguer::Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .with_initial_text(current.to_string())
+            .allow_empty(true)
+            .interact_text()
+            .map_err(failed)
+    }
+
+    /// Lets the user pick one of `items` with arrow keys, returns its index.
+    pub fn select<T: ToString>(
+        &self,
+        prompt: &str,
+        items: &[T],
+        default: usize,
+    ) -> Result<Option<usize>> {
+        if !self.interactive {
+            return Ok(None);
+        }
+        dialoguer::Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(items)
+            .default(default)
+            .interact_opt()
+            .map_err(failed)?
+            .map(Some)
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Lets the user fuzzy search through `items`, returns the index of the
+    /// picked one.
+    pub fn pick<T: ToString>(&self, prompt: &str, items: &[T], what: &str) -> Result<usize> {
+        self.require(what)?;
+        if items.is_empty() {
+            return Err(Error::NothingToPick(
+                prompt.trim_end_matches(':').to_owned(),
+            ));
+        }
+        dialoguer::FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(items)
+            .default(0)
+            .interact_opt()
+            .map_err(failed)?
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Lets the user toggle any of `items`, returns the indices of the
+    /// checked ones.
+    pub fn multi_select<T: ToString>(
+        &self,
+        prompt: &str,
+        items: &[T],
+        defaults: &[bool],
+    ) -> Result<Option<Vec<usize>>> {
+        if !self.interactive || items.is_empty() {
+            return Ok(None);
+        }
+        dialoguer::MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(items)
+            .defaults(defaults)
+            .interact_opt()
+            .map_err(failed)?
+            .map(Some)
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Asks a yes/no question.
+    pub fn confirm(&self, prompt: &str, default: bool) -> Result<Option<bool>> {
+        if !self.interactive {
+            return Ok(None);
+        }
+        dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .default(default)
+            .interact_opt()
+            .map_err(failed)?
+            .map(Some)
+            .ok_or(Error::Cancelled)
+    }
+
+    /// Asks before doing something destructive, unless `yes` was given.
+    /// Declining cancels the command like dismissing the prompt does.
+    pub fn confirm_destructive(&self, prompt: &str, yes: bool) -> Result<()> {
+        if yes {
+            return Ok(());
+        }
+        self.require("--yes")?;
+        match self.confirm(prompt, false)? {
+            Some(true) => Ok(()),
+            _ => Err(Error::Cancelled),
+        }
+    }
+}
+
+/// Ctrl-C while a prompt is open shows up as an interrupted read.
+fn failed(e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::Interrupted {
+        Error::Cancelled
+    } else {
+        Error::Prompt(e)
+    }
+}
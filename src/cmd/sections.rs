@@ -0,0 +1,87 @@
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+
+use crate::api;
+use crate::cache::{Kind, Store};
+
+use super::output::{self, Output};
+use super::pick_project;
+use super::{Prompt, Result};
+
+pub struct Sections<'a> {
+    pub(super) client: &'a api::Client,
+    pub(super) store: &'a Store,
+    pub(super) output: Output,
+    pub(super) prompt: Prompt,
+}
+
+impl Sections<'_> {
+    pub async fn list(&self, project: &Option<String>) -> Result<()> {
+        let mut sections = match project {
+            Some(id) => self.client.section_list(Some(id)).await?,
+            None => self.store.sections(self.client).await?,
+        };
+        sections.sort_by(|a, b| (&a.project_id, a.order).cmp(&(&b.project_id, b.order)));
+
+        if !self.output.is_table() {
+            output::print_items(self.output, &sections)?;
+            return Ok(());
+        }
+
+        let index = self.store.index(self.client).await?;
+        let mut table = Table::new();
+        table
+            .set_header(vec!["ID", "Project", "Section"])
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS);
+
+        for section in sections {
+            table.add_row(vec![
+                section.id.to_owned(),
+                index.project_name(&section.project_id).to_owned(),
+                section.name,
+            ]);
+        }
+        println!("{table}");
+        Ok(())
+    }
+
+    pub async fn create(&self, name: &str, project: &Option<String>) -> Result<()> {
+        let project_id = match project {
+            Some(id) => id.to_owned(),
+            None => {
+                pick_project(self.client, self.store, &self.prompt, "--project")
+                    .await?
+                    .id
+            }
+        };
+
+        let section = self
+            .client
+            .section_create(api::SectionCreate::new(name.to_owned(), project_id))
+            .await?;
+        self.store.invalidate(Kind::Sections);
+        println!("{} | {}", section.id, section.name);
+        Ok(())
+    }
+
+    pub async fn rename(&self, id: &str, name: &str) -> Result<()> {
+        let section = self.client.section_update(id, name.to_owned()).await?;
+        self.store.invalidate(Kind::Sections);
+        println!("{} | {}", section.id, section.name);
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: &str, yes: bool) -> Result<()> {
+        let section = self.client.section_view(id).await?;
+
+        self.prompt.confirm_destructive(
+            &format!("Delete section \"{}\" and all its tasks?", section.name),
+            yes,
+        )?;
+
+        self.client.section_delete(id).await?;
+        self.store.invalidate(Kind::Sections);
+        println!("section deleted");
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Local, TimeDelta};
+
+use crate::api;
+
+/// Formats the due date in local time, with the time only if the task has one.
+pub fn date(due: &api::Due) -> String {
+    match due.datetime {
+        Some(_) => due.at().format("%Y-%m-%d %H:%M").to_string(),
+        None => due.date.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// Describes when the task is due relative to `now`, e.g. `in 2h`, `tomorrow`
+/// or `3 days overdue`.
+pub fn relative(due: &api::Due, now: DateTime<Local>) -> String {
+    if due.datetime.is_none() {
+        return match (due.date - now.date_naive()).num_days() {
+            0 => String::from("today"),
+            1 => String::from("tomorrow"),
+            days if days < 0 => format!("{} overdue", plural(-days, "day")),
+            days => format!("in {}", plural(days, "day")),
+        };
+    }
+
+    let delta = due.at() - now;
+    if delta < TimeDelta::zero() {
+        format!("{} overdue", span(-delta))
+    } else {
+        format!("in {}", span(delta))
+    }
+}
+
+fn span(delta: TimeDelta) -> String {
+    if delta.num_hours() < 1 {
+        format!("{}m", delta.num_minutes())
+    } else if delta.num_days() < 1 {
+        format!("{}h", delta.num_hours())
+    } else {
+        plural(delta.num_days(), "day")
+    }
+}
+
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("{count} {unit}")
+    } else {
+        format!("{count} {unit}s")
+    }
+}
@@ -0,0 +1,34 @@
+use std::collections::{HashMap, HashSet};
+
+/// Orders `items` depth first so children follow their parent, siblings
+/// sorted by `order`. Returns every item with its depth, items whose parent
+/// is not in `items` are treated as roots.
+pub fn flatten<T>(
+    items: &[T],
+    id: impl Fn(&T) -> &str,
+    parent: impl Fn(&T) -> Option<&str>,
+    order: impl Fn(&T) -> i64,
+) -> Vec<(usize, &T)> {
+    let ids: HashSet<&str> = items.iter().map(&id).collect();
+    let mut children: HashMap<Option<&str>, Vec<&T>> = HashMap::new();
+    for item in items {
+        let parent = parent(item).filter(|p| ids.contains(p));
+        children.entry(parent).or_default().push(item);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|item| order(item));
+    }
+
+    let mut flat = Vec::with_capacity(items.len());
+    let mut stack: Vec<(usize, &T)> = children
+        .get(&None)
+        .map(|roots| roots.iter().rev().map(|item| (0, *item)).collect())
+        .unwrap_or_default();
+    while let Some((depth, item)) = stack.pop() {
+        flat.push((depth, item));
+        if let Some(kids) = children.get(&Some(id(item))) {
+            stack.extend(kids.iter().rev().map(|kid| (depth + 1, *kid)));
+        }
+    }
+    flat
+}
@@ -1,23 +1,487 @@
 use figment::{
-    providers::{Format, Yaml},
+    providers::{Format, Serialized, Yaml},
+    value::{Dict, Value},
     Figment,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::secret::{self, Backend, Secrets};
+use crate::{api, oauth};
+
+/// Environment variable holding the passphrase of the secrets file.
+pub const PASSPHRASE_ENV: &str = "TODOIST_SECRET_PASSPHRASE";
+
+/// Account name of the top level settings, for the cache and secret stores.
+/// No profile can have it, so the two never share a token or cache.
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Environment variable selecting the profile when `--profile` is not given.
+pub const PROFILE_ENV: &str = "TODOIST_PROFILE";
+
+const ENV_PREFIX: &str = "TODOIST_";
+
+/// `TODOIST_` environment variables the config is read with. The CLI takes
+/// them from the process, tests build their own so the shell does not leak in.
+#[derive(Default, Debug, Clone)]
+pub struct Vars {
+    vars: BTreeMap<String, String>,
+}
+
+impl Vars {
+    pub fn new() -> Vars {
+        Vars::default()
+    }
+
+    pub fn process() -> Vars {
+        Vars {
+            vars: std::env::vars()
+                .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+                .collect(),
+        }
+    }
+
+    /// Sets the variable `name`, e.g. `TODOIST_BASE_URL`.
+    pub fn with(mut self, name: &str, value: &str) -> Vars {
+        self.vars.insert(name.to_owned(), value.to_owned());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// Settings of `ENV_KEYS` the variables override. Only the ones in
+    /// `NUMBER_KEYS` are read as numbers, a text setting stays text even if
+    /// it looks like a number.
+    fn settings(&self) -> Dict {
+        ENV_KEYS
+            .iter()
+            .filter_map(|key| {
+                let value = self.get(&format!("{ENV_PREFIX}{}", key.to_uppercase()))?;
+                let value = match NUMBER_KEYS.contains(key) {
+                    // Not a number is left as text and rejected when the config is read.
+                    true => value
+                        .parse::<u64>()
+                        .map(Value::from)
+                        .unwrap_or_else(|_| Value::from(value.to_owned())),
+                    false => Value::from(value.to_owned()),
+                };
+                Some((key.to_string(), value))
+            })
+            .collect()
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct Config {
+    /// API token, empty if it is kept in a secret backend.
+    #[serde(default)]
     pub api_key: String,
+    /// Where the API token is kept.
+    #[serde(default)]
+    pub secret_backend: Backend,
+    /// Todoist app `auth oauth` logs in with.
+    #[serde(default)]
+    pub oauth: oauth::Settings,
+    /// Base URL every API request is built from, can be overridden with `TODOIST_BASE_URL`.
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// Base URL of the Sync API used by quick add, can be overridden with
+    /// `TODOIST_SYNC_BASE_URL`.
+    #[serde(default = "default_sync_base_url")]
+    pub sync_base_url: String,
+    /// How failed requests are retried.
+    #[serde(default)]
+    pub retry: api::RetryPolicy,
+    /// Seconds cached projects and labels are used before being fetched again.
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl: u64,
+    /// Name of the project tasks are created in when none is given.
+    pub default_project: Option<String>,
+    /// Filter `tasks list` uses when none is given.
+    pub default_filter: Option<String>,
+    /// Name of the profile whose settings replace the ones above.
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Settings of one account, each one replaces the top level setting of the
+/// same name.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_filter: Option<String>,
+}
+
+/// The profiles of a config file, readable even if the rest of it is not.
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct Profiles {
+    /// Profile selected in the file.
+    #[serde(rename = "profile")]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Config {
+    /// Secret store the token is kept in, `None` if it is kept in the config
+    /// file at `path`. `passphrase` unlocks the secrets file next to it.
+    pub fn secrets(
+        &self,
+        path: &Path,
+        passphrase: Option<String>,
+    ) -> Result<Option<Secrets>, Box<dyn std::error::Error>> {
+        Ok(match self.secret_backend {
+            Backend::Config => None,
+            Backend::Keyring => Some(Secrets::Keyring),
+            Backend::File => Some(Secrets::File {
+                path: path.with_file_name("secrets.enc"),
+                passphrase: passphrase.ok_or_else(|| {
+                    format!("the file secret backend needs a passphrase in {PASSPHRASE_ENV}")
+                })?,
+                rounds: secret::ROUNDS,
+            }),
+        })
+    }
+
+    /// Name the token is stored under in a secret store, one per profile.
+    pub fn account(&self) -> &str {
+        self.profile.as_deref().unwrap_or(DEFAULT_ACCOUNT)
+    }
+}
+
+impl Profiles {
+    pub fn read(path: &Path) -> Result<Profiles, Box<dyn std::error::Error>> {
+        let profiles: Profiles = Figment::new().merge(Yaml::file(path)).extract()?;
+        if profiles.profiles.contains_key(DEFAULT_ACCOUNT) {
+            return Err(format!(
+                "`{DEFAULT_ACCOUNT}` cannot be a profile name, it is used for the top level settings"
+            )
+            .into());
+        }
+        Ok(profiles)
+    }
+}
+
+fn default_base_url() -> String {
+    api::DEFAULT_BASE_URL.to_string()
+}
+
+fn default_sync_base_url() -> String {
+    api::DEFAULT_SYNC_BASE_URL.to_string()
+}
+
+fn default_cache_ttl() -> u64 {
+    600
+}
+
+/// Settings that can be overridden with `TODOIST_` environment variables,
+/// e.g. `TODOIST_API_KEY`.
+const ENV_KEYS: [&str; 7] = [
+    "api_key",
+    "secret_backend",
+    "base_url",
+    "sync_base_url",
+    "cache_ttl",
+    "default_project",
+    "default_filter",
+];
+
+/// Settings `config set` accepts as text.
+const TEXT_KEYS: [&str; 12] = [
+    "api_key",
+    "secret_backend",
+    "base_url",
+    "sync_base_url",
+    "default_project",
+    "default_filter",
+    "profile",
+    "oauth.client_id",
+    "oauth.client_secret",
+    "oauth.authorize_url",
+    "oauth.token_url",
+    "oauth.scope",
+];
+
+/// Settings `config set` accepts as whole numbers.
+const NUMBER_KEYS: [&str; 5] = [
+    "cache_ttl",
+    "retry.max_retries",
+    "retry.base_delay_ms",
+    "retry.max_delay_ms",
+    "oauth.port",
+];
+
+/// Location of the config file, `todoist/config.yaml` in the user's config
+/// directory. A config at the old `~/.config/api/config.yaml` is moved there
+/// and the directory is created if missing.
+pub fn locate() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = dirs::config_dir()
+        .ok_or("failed to find the config directory")?
+        .join("todoist/config.yaml");
+    if let Some(home) = dirs::home_dir() {
+        let legacy = home.join(".config/api/config.yaml");
+        if migrate(&legacy, &path)? {
+            eprintln!(
+                "moved the config from {} to {}",
+                legacy.display(),
+                path.display()
+            );
+        }
+    }
+    if let Some(config_dir) = path.parent() {
+        fs::create_dir_all(config_dir)?;
+    }
+    Ok(path)
+}
+
+/// Moves the config at `legacy` to `path` unless there already is one.
+/// Returns whether anything was moved.
+pub fn migrate(legacy: &Path, path: &Path) -> io::Result<bool> {
+    if path.exists() || !legacy.is_file() {
+        return Ok(false);
+    }
+    if let Some(config_dir) = path.parent() {
+        fs::create_dir_all(config_dir)?;
+    }
+    if fs::rename(legacy, path).is_err() {
+        fs::copy(legacy, path)?;
+        fs::remove_file(legacy)?;
+    }
+    // Only removes the old directory if nothing else is in it.
+    if let Some(legacy_dir) = legacy.parent() {
+        let _ = fs::remove_dir(legacy_dir);
+    }
+    Ok(true)
 }
 
-pub fn setup_config() -> Result<Config, Box<dyn std::error::Error>> {
-    let home = dirs::home_dir().expect("failed to get home directory");
-    let config_dir = Path::new(&home).join(".config/api");
-    if !config_dir.is_dir() {
-        fs::create_dir(config_dir.to_owned()).expect("failed to create config directory");
+/// Loads the config with the settings of `profile`, or of the profile
+/// selected in the config file if `None`. The token is read from the secret
+/// backend unless the config or environment has one.
+pub fn setup_config(
+    profile: Option<String>,
+    vars: &Vars,
+) -> Result<Config, Box<dyn std::error::Error>> {
+    load_with_token(&locate()?, profile.as_deref(), vars)
+}
+
+/// Same as `setup_config` for the config at `path`.
+pub fn load_with_token(
+    path: &Path,
+    profile: Option<&str>,
+    vars: &Vars,
+) -> Result<Config, Box<dyn std::error::Error>> {
+    let mut config = load(path, profile, vars)?;
+    if config.api_key.is_empty() {
+        let passphrase = vars.get(PASSPHRASE_ENV).map(str::to_owned);
+        if let Some(secrets) = config.secrets(path, passphrase)? {
+            config.api_key = secrets.get(config.account())?.unwrap_or_default();
+        }
+    }
+    if config.api_key.is_empty() {
+        return Err("no API token, log in with `todoist auth login`".into());
+    }
+    Ok(config)
+}
+
+/// Reads the config at `path`. The settings of `profile`, or of the profile
+/// selected in the file if `None`, replace the top level ones, `vars`
+/// replace both.
+pub fn load(
+    path: &Path,
+    profile: Option<&str>,
+    vars: &Vars,
+) -> Result<Config, Box<dyn std::error::Error>> {
+    let mut config: Config = figment(path, profile, vars)?.extract()?;
+    // With a secret backend every profile keeps its token there, a plain text
+    // one at the top level belongs to the default account and must not be
+    // used for another one.
+    if config.secret_backend != Backend::Config {
+        if let Some(name) = &config.profile {
+            let own = config.profiles.get(name).and_then(|p| p.api_key.to_owned());
+            config.api_key = vars
+                .get("TODOIST_API_KEY")
+                .map(str::to_owned)
+                .or(own)
+                .unwrap_or_default();
+        }
+    }
+    Ok(config)
+}
+
+/// Effective value of `key` for `profile`, nested keys are separated by dots
+/// like `retry.max_retries`. Values that are not text are shown as YAML.
+pub fn get(
+    path: &Path,
+    profile: Option<&str>,
+    key: &str,
+    vars: &Vars,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    // Settings left out of the file have their default value.
+    let defaults: Config = Figment::new().extract()?;
+    let figment =
+        Figment::from(Serialized::defaults(defaults)).merge(figment(path, profile, vars)?);
+    let value = match figment.find_value(key) {
+        Ok(Value::Empty(..)) => return Ok(None),
+        Ok(value) => value,
+        Err(e) if e.missing() => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(Some(match value.as_str() {
+        Some(text) => text.to_owned(),
+        None => serde_yaml::to_string(&value)?.trim_end().to_owned(),
+    }))
+}
+
+/// Writes `key` into the file, under `profile` or the profile selected in
+/// the file if there is one. Other settings are kept, comments are not.
+pub fn set(
+    path: &Path,
+    profile: Option<&str>,
+    key: &str,
+    value: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let value: serde_yaml::Value = if TEXT_KEYS.contains(&key) {
+        value.into()
+    } else if NUMBER_KEYS.contains(&key) {
+        value
+            .parse::<u64>()
+            .map_err(|_| format!("`{key}` has to be a whole number"))?
+            .into()
+    } else {
+        return Err(format!(
+            "unknown key `{key}`, expected one of {}, {}",
+            TEXT_KEYS.join(", "),
+            NUMBER_KEYS.join(", ")
+        )
+        .into());
+    };
+    if key == "profile" {
+        return use_profile(path, value.as_str().unwrap_or_default());
+    }
+
+    let mut keys = match selected(path, profile)? {
+        Some(name) => vec![String::from("profiles"), name],
+        None => Vec::new(),
+    };
+    keys.extend(key.split('.').map(str::to_owned));
+    write(path, &keys, Some(value))?;
+    Ok(())
+}
+
+/// Removes `key` from the file, from the same place `set` writes it to.
+/// Returns whether it was there.
+pub fn unset(
+    path: &Path,
+    profile: Option<&str>,
+    key: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut keys = match selected(path, profile)? {
+        Some(name) => vec![String::from("profiles"), name],
+        None => Vec::new(),
+    };
+    keys.extend(key.split('.').map(str::to_owned));
+    write(path, &keys, None)
+}
+
+/// Selects `profile` in the config file at `path`. Other settings are kept,
+/// comments in the file are not.
+pub fn use_profile(path: &Path, profile: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !Profiles::read(path)?.profiles.contains_key(profile) {
+        return Err(format!("unknown profile `{profile}`").into());
+    }
+    write(path, &[String::from("profile")], Some(profile.into()))?;
+    Ok(())
+}
+
+/// Settings of the file with the ones of the selected profile and the
+/// environment on top.
+fn figment(
+    path: &Path,
+    profile: Option<&str>,
+    vars: &Vars,
+) -> Result<Figment, Box<dyn std::error::Error>> {
+    let file = Figment::new().merge(Yaml::file(path));
+    let mut figment = file.clone();
+    if let Some(name) = selected(path, profile)? {
+        figment = figment
+            .merge(file.focus(&format!("profiles.{name}")))
+            .merge(Serialized::default("profile", name));
+    }
+    Ok(figment.merge(Serialized::defaults(vars.settings())))
+}
+
+/// Name of the profile in use, `profile` or else the one selected in the file.
+fn selected(
+    path: &Path,
+    profile: Option<&str>,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let profiles = Profiles::read(path)?;
+    match profile.map(str::to_owned).or(profiles.active) {
+        Some(name) if !profiles.profiles.contains_key(&name) => {
+            Err(format!("unknown profile `{name}`").into())
+        }
+        name => Ok(name),
+    }
+}
+
+/// Sets the value at `keys` in the file, creating the file and any missing
+/// mappings on the way, or removes it if `value` is `None`. Returns whether
+/// the file changed.
+fn write(
+    path: &Path,
+    keys: &[String],
+    value: Option<serde_yaml::Value>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut file = match fs::read_to_string(path) {
+        Ok(content) => serde_yaml::from_str::<Option<serde_yaml::Value>>(&content)?
+            .unwrap_or_else(|| serde_yaml::Mapping::new().into()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && value.is_none() => return Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => serde_yaml::Mapping::new().into(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let (last, parents) = keys.split_last().ok_or("no key given")?;
+    let mut mapping = file
+        .as_mapping_mut()
+        .ok_or_else(|| format!("{} does not hold a mapping", path.display()))?;
+    for key in parents {
+        if value.is_none() && !mapping.contains_key(key.as_str()) {
+            return Ok(false);
+        }
+        let entry = mapping
+            .entry(key.as_str().into())
+            .or_insert_with(|| serde_yaml::Mapping::new().into());
+        if entry.is_null() {
+            *entry = serde_yaml::Mapping::new().into();
+        }
+        mapping = entry
+            .as_mapping_mut()
+            .ok_or_else(|| format!("`{key}` in {} is not a mapping", path.display()))?;
+    }
+    let changed = match value {
+        Some(value) => {
+            mapping.insert(last.as_str().into(), value);
+            true
+        }
+        None => mapping.remove(last.as_str()).is_some(),
+    };
+
+    if changed {
+        fs::write(path, serde_yaml::to_string(&file)?)?;
     }
-    Ok(Figment::new()
-        .merge(Yaml::file(config_dir.join("config.yaml")))
-        .extract()?)
+    Ok(changed)
 }
@@ -0,0 +1,7 @@
+pub mod api;
+pub mod cache;
+pub mod cmd;
+pub mod config;
+pub mod oauth;
+pub mod quick_add;
+pub mod secret;
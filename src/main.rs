@@ -1,66 +1,253 @@
-mod api;
-mod cmd;
-mod config;
+use std::io::{self, IsTerminal};
 
-use std::io;
-
-use crate::cmd::Cmd;
-use crate::config::setup_config;
-use clap::{Args, Command, CommandFactory, Parser, Subcommand};
+use clap::{ArgGroup, Args, Command, CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Generator, Shell};
+use std::time::Duration;
 
-extern crate dirs;
+use todoist::api;
+use todoist::cache::Store;
+use todoist::cmd::layout::{Column, GroupBy, Sort, DEFAULT_COLUMNS};
+use todoist::cmd::{self, output::Output, Cmd, Prompt};
+use todoist::config::{self, setup_config};
 
 fn print_completions<G: Generator>(gen: G, cmd: &mut Command) {
     generate(gen, cmd, cmd.get_name().to_string(), &mut io::stdout());
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let config = setup_config()?;
-    let client = api::Client::new(reqwest::Client::new(), config.api_key);
-
+async fn main() {
     let cli = Cli::parse();
 
     if let Some(generator) = cli.generator {
         let mut cmd = Cli::command();
         eprintln!("Generating completion file for {generator:?}...");
         print_completions(generator, &mut cmd);
-    } else {
-        let cmd = Cmd::new(&client);
-
-        if let Some(command) = cli.command {
-            match command {
-                Commands::Tasks(tasks) => match &tasks.command {
-                    TaskCommands::List { filter, raw } => cmd.tasks.list(filter, raw).await,
-                    TaskCommands::Create {
-                        content,
-                        due,
-                        project,
-                        labels,
-                        priority,
-                    } => {
-                        cmd.tasks
-                            .create(content, due, project, labels, priority)
-                            .await
-                    }
-                    TaskCommands::Done { id } => cmd.tasks.done(id).await,
-                    TaskCommands::View { id } => cmd.tasks.view(id).await,
-                },
-                Commands::Projects(projects) => match &projects.command {
-                    ProjectCommands::List {} => cmd.projects.list().await,
-                    ProjectCommands::View { id } => cmd.projects.view(id).await,
-                },
-            }
-        } else {
-            match Cli::command().print_help() {
-                Ok(_) => {}
-                Err(err) => eprintln!("{}", err),
+        return;
+    }
+    let Some(command) = cli.command else {
+        if let Err(err) = Cli::command().print_help() {
+            eprintln!("{}", err);
+        }
+        return;
+    };
+
+    let vars = config::Vars::process();
+    let profile = cli
+        .profile
+        .clone()
+        .or_else(|| vars.get(config::PROFILE_ENV).map(str::to_owned));
+    // Prompts need someone to answer them, so only ask when attached to a terminal.
+    let interactive = !cli.no_input && io::stdin().is_terminal() && io::stderr().is_terminal();
+
+    if let Commands::Config(_) | Commands::Auth(_) = &command {
+        let result = match config::locate() {
+            Ok(path) => {
+                let auth = cmd::Auth::new(
+                    path.to_owned(),
+                    profile.to_owned(),
+                    vars.to_owned(),
+                    Prompt::new(interactive),
+                );
+                let cmd = cmd::Config::new(path, profile, vars, cli.output);
+                match &command {
+                    Commands::Auth(auth_command) => match &auth_command.command {
+                        AuthCommands::Login { token } => auth.login(token).await,
+                        AuthCommands::Oauth {
+                            client_id,
+                            client_secret,
+                            no_browser,
+                        } => auth.oauth(client_id, client_secret, !no_browser).await,
+                        AuthCommands::Logout {} => auth.logout(),
+                    },
+                    Commands::Config(config) => match &config.command {
+                        ConfigCommands::Init { token } => auth.login(token).await,
+                        ConfigCommands::Path {} => {
+                            cmd.path();
+                            Ok(())
+                        }
+                        ConfigCommands::Get { key } => cmd.get(key),
+                        ConfigCommands::Set { key, value } => cmd.set(key, value),
+                        ConfigCommands::Profiles(profiles) => match &profiles.command {
+                            ProfileCommands::List {} => cmd.profiles_list(),
+                            ProfileCommands::Use { name } => cmd.profiles_use(name),
+                        },
+                    },
+                    _ => Ok(()),
+                }
             }
+            Err(err) => Err(cmd::Error::Config(err)),
+        };
+        if let Err(err) = result {
+            eprintln!("Error: {}", err);
+            std::process::exit(err.exit_code());
         }
+        return;
+    }
+
+    let config = match setup_config(profile, &vars) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Error: failed to load the config: {}", err);
+            std::process::exit(cmd::Error::Config(err).exit_code());
+        }
+    };
+    // Every profile is a different account, so each gets its own cache. Without
+    // a profile it goes to `default` so clearing it leaves the others alone.
+    let cache_dir = Store::default_dir().join(config.account());
+
+    let client = api::Client::new(reqwest::Client::new(), config.api_key, config.base_url)
+        .with_sync_base_url(config.sync_base_url)
+        .with_retry(config.retry);
+    let mut store = Store::new(cache_dir, Duration::from_secs(config.cache_ttl));
+    if cli.no_cache {
+        store = store.bypass();
     }
+    let cmd = Cmd::new(&client, &store, cli.output, Prompt::new(interactive))
+        .with_default_project(config.default_project)
+        .with_default_filter(config.default_filter);
 
-    Ok(())
+    let result = match command {
+        Commands::Tasks(tasks) => match &tasks.command {
+            TaskCommands::List {
+                filter,
+                sort,
+                group_by,
+                columns,
+            } => cmd.tasks.list(filter, *sort, *group_by, columns).await,
+            TaskCommands::Create {
+                content,
+                due,
+                project,
+                section,
+                parent,
+                labels,
+                priority,
+            } => {
+                cmd.tasks
+                    .create(content, due, project, section, parent, labels, priority)
+                    .await
+            }
+            TaskCommands::Edit {
+                id,
+                content,
+                description,
+                due,
+                due_date,
+                due_datetime,
+                priority,
+                labels,
+                assignee,
+                duration,
+                duration_unit,
+            } => {
+                let update = api::TaskUpdate {
+                    content: content.to_owned(),
+                    description: description.to_owned(),
+                    due_string: due.to_owned(),
+                    due_date: due_date.to_owned(),
+                    due_datetime: due_datetime.to_owned(),
+                    priority: priority.to_owned(),
+                    labels: (!labels.is_empty()).then(|| labels.to_owned()),
+                    assignee_id: assignee.to_owned(),
+                    duration: duration.to_owned(),
+                    duration_unit: duration.map(|_| duration_unit.unwrap_or_default()),
+                };
+                cmd.tasks.edit(id, &update).await
+            }
+            TaskCommands::Done { id } => cmd.tasks.done(id).await,
+            TaskCommands::Reopen { id } => cmd.tasks.reopen(id).await,
+            TaskCommands::Delete { id, yes } => cmd.tasks.delete(id, *yes).await,
+            TaskCommands::View { id } => cmd.tasks.view(id).await,
+        },
+        Commands::Projects(projects) => match &projects.command {
+            ProjectCommands::List { counts } => cmd.projects.list(*counts).await,
+            ProjectCommands::View { id } => cmd.projects.view(id).await,
+            ProjectCommands::Create {
+                name,
+                color,
+                parent,
+                favorite,
+                view_style,
+            } => {
+                cmd.projects
+                    .create(name, color, parent, favorite, view_style)
+                    .await
+            }
+            ProjectCommands::Edit {
+                id,
+                name,
+                color,
+                parent,
+                root,
+                favorite,
+                view_style,
+            } => {
+                let update = api::ProjectUpdate {
+                    name: name.to_owned(),
+                    color: color.to_owned(),
+                    is_favorite: favorite.to_owned(),
+                    view_style: view_style.to_owned(),
+                };
+                let parent = match root {
+                    true => Some(None),
+                    false => parent.to_owned().map(Some),
+                };
+                cmd.projects.edit(id, &update, &parent).await
+            }
+            ProjectCommands::Archive { id } => cmd.projects.archive(id).await,
+            ProjectCommands::Unarchive { id } => cmd.projects.unarchive(id).await,
+            ProjectCommands::Delete { id, yes } => cmd.projects.delete(id, *yes).await,
+        },
+        Commands::Sections(sections) => match &sections.command {
+            SectionCommands::List { project } => cmd.sections.list(project).await,
+            SectionCommands::Create { name, project } => cmd.sections.create(name, project).await,
+            SectionCommands::Rename { id, name } => cmd.sections.rename(id, name).await,
+            SectionCommands::Delete { id, yes } => cmd.sections.delete(id, *yes).await,
+        },
+        Commands::Comments(comments) => match &comments.command {
+            CommentCommands::List { target } => cmd.comments.list(&target.into()).await,
+            CommentCommands::Add { content, target } => {
+                cmd.comments.add(content, &target.into()).await
+            }
+            CommentCommands::Edit { id, content } => cmd.comments.edit(id, content).await,
+            CommentCommands::Delete { id, yes } => cmd.comments.delete(id, *yes).await,
+        },
+        Commands::Labels(labels) => match &labels.command {
+            LabelCommands::List { shared } => cmd.labels.list(*shared).await,
+            LabelCommands::Create {
+                name,
+                color,
+                favorite,
+            } => cmd.labels.create(name, color, *favorite).await,
+            LabelCommands::Rename {
+                label,
+                name,
+                shared,
+            } => cmd.labels.rename(label, name, *shared).await,
+            LabelCommands::Recolor { label, color } => cmd.labels.recolor(label, color).await,
+            LabelCommands::Favorite { label } => cmd.labels.favorite(label).await,
+            LabelCommands::Delete { label, shared, yes } => {
+                cmd.labels.delete(label, *shared, *yes).await
+            }
+        },
+        Commands::Cache(cache) => match &cache.command {
+            CacheCommands::Refresh {} => cmd.cache.refresh().await,
+            CacheCommands::Clear {} => cmd.cache.clear(),
+            CacheCommands::Status {} => cmd.cache.status(),
+        },
+        Commands::Add {
+            text,
+            dry_run,
+            local,
+        } => cmd.tasks.quick_add(&text, dry_run, local).await,
+        // Handled above, before the config is loaded.
+        Commands::Config(_) | Commands::Auth(_) => Ok(()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        std::process::exit(err.exit_code());
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -69,6 +256,30 @@ enum Commands {
     Tasks(Tasks),
     // Work with projects
     Projects(Projects),
+    /// Work with sections
+    Sections(Sections),
+    /// Work with comments on tasks and projects
+    Comments(Comments),
+    /// Work with labels
+    Labels(Labels),
+    /// Manage the local cache of projects, labels and sections
+    Cache(Cache),
+    /// Manage the config file
+    Config(Config),
+    /// Store or remove the API token
+    Auth(Auth),
+    #[clap(about = "Quick add a task, e.g. \"Pay rent tomorrow 9am #Home @finance p1\"")]
+    Add {
+        /// Task with #project, @labels, p1-p4 and a due date in natural language
+        text: String,
+        /// Show how the text would be read without creating the task
+        #[clap(long)]
+        dry_run: bool,
+        /// Read the text locally instead of with Todoist's quick add, offers
+        /// to create projects, sections and labels that do not exist
+        #[clap(long)]
+        local: bool,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -85,9 +296,15 @@ enum TaskCommands {
         /// Filter tasks using todoist query syntax
         #[clap(long, short)]
         filter: Option<String>,
-        /// If provided, outputs raw data
-        #[clap(long, short)]
-        raw: Option<bool>,
+        /// Order of the tasks
+        #[clap(long, short, value_enum, default_value_t = Sort::Due)]
+        sort: Sort,
+        /// Group the tasks under a header per value
+        #[clap(long, short, value_enum)]
+        group_by: Option<GroupBy>,
+        /// Comma separated columns of the table
+        #[clap(long, short, value_enum, value_delimiter = ',', default_values_t = DEFAULT_COLUMNS)]
+        columns: Vec<Column>,
     },
     // Create a task
     #[clap(about = "Create a task")]
@@ -100,12 +317,54 @@ enum TaskCommands {
         /// Tasks project
         #[clap(long, short)]
         project: Option<String>,
+        /// ID of the section to put the task in
+        #[clap(long, short)]
+        section: Option<String>,
+        /// ID of the parent task, pick one if the flag has no value
+        #[clap(long, num_args = 0..=1)]
+        parent: Option<Option<String>>,
         /// Lables to add to task
         #[clap(long, short)]
         labels: Vec<String>,
         /// Priority of the task
         #[clap(long)]
-        priority: Option<u8>,
+        priority: Option<api::Priority>,
+    },
+    // Edit a task, prompts for every field if no flags are given
+    #[clap(about = "Edit a task")]
+    Edit {
+        /// ID of the task
+        id: String,
+        /// New content of the task
+        #[clap(long, short)]
+        content: Option<String>,
+        /// New description of the task
+        #[clap(long)]
+        description: Option<String>,
+        /// Due date in natural language, `no date` removes it
+        #[clap(long, short, conflicts_with_all = ["due_date", "due_datetime"])]
+        due: Option<String>,
+        /// Due date as YYYY-MM-DD
+        #[clap(long, conflicts_with = "due_datetime")]
+        due_date: Option<chrono::NaiveDate>,
+        /// Due date and time in RFC 3339 format
+        #[clap(long)]
+        due_datetime: Option<chrono::DateTime<chrono::FixedOffset>>,
+        /// Priority of the task
+        #[clap(long)]
+        priority: Option<api::Priority>,
+        /// Labels of the task, replaces the current ones
+        #[clap(long, short)]
+        labels: Vec<String>,
+        /// ID of the user the task is assigned to
+        #[clap(long)]
+        assignee: Option<String>,
+        /// Duration of the task
+        #[clap(long)]
+        duration: Option<u32>,
+        /// Unit of the duration, `minute` or `day`
+        #[clap(long, requires = "duration")]
+        duration_unit: Option<api::DurationUnit>,
     },
     // Mark task as done
     #[clap(about = "Mark task as done")]
@@ -113,6 +372,21 @@ enum TaskCommands {
         /// ID of the task
         id: Option<String>,
     },
+    // Reopen a completed task
+    #[clap(about = "Reopen a completed task")]
+    Reopen {
+        /// ID of the task, pick one of the recently completed ones if missing
+        id: Option<String>,
+    },
+    // Delete a task
+    #[clap(about = "Delete a task")]
+    Delete {
+        /// ID of the task
+        id: Option<String>,
+        /// Skip the confirmation prompt
+        #[clap(long, short)]
+        yes: bool,
+    },
     // View task by id
     #[clap(about = "View task by id")]
     View {
@@ -129,10 +403,327 @@ struct Projects {
 
 #[derive(Debug, Subcommand)]
 enum ProjectCommands {
-    // List command
-    List {},
+    #[clap(about = "List projects as a tree")]
+    List {
+        /// Show the number of open tasks in every project
+        #[clap(long, short)]
+        counts: bool,
+    },
     // View project
-    View { id: String },
+    View {
+        id: String,
+    },
+    #[clap(about = "Create a project, prompts for missing options")]
+    Create {
+        /// Name of the project
+        name: Option<String>,
+        /// Color of the project
+        #[clap(long, short, value_parser = api::COLORS)]
+        color: Option<String>,
+        /// ID of the parent project
+        #[clap(long, short)]
+        parent: Option<String>,
+        /// Mark the project as favorite
+        #[clap(long, num_args = 0..=1, default_missing_value = "true")]
+        favorite: Option<bool>,
+        /// How the project is shown
+        #[clap(long, value_parser = api::VIEW_STYLES)]
+        view_style: Option<String>,
+    },
+    #[clap(about = "Edit a project, prompts for every field if no flags are given")]
+    Edit {
+        /// ID of the project, pick one if missing
+        id: Option<String>,
+        /// New name of the project
+        #[clap(long, short)]
+        name: Option<String>,
+        /// New color of the project
+        #[clap(long, short, value_parser = api::COLORS)]
+        color: Option<String>,
+        /// ID of the project to move it under
+        #[clap(long, short)]
+        parent: Option<String>,
+        /// Move the project to the top level
+        #[clap(long, conflicts_with = "parent")]
+        root: bool,
+        /// Mark or unmark the project as favorite
+        #[clap(long, num_args = 0..=1, default_missing_value = "true")]
+        favorite: Option<bool>,
+        /// How the project is shown
+        #[clap(long, value_parser = api::VIEW_STYLES)]
+        view_style: Option<String>,
+    },
+    #[clap(about = "Archive a project and its sub-projects")]
+    Archive {
+        /// ID of the project, pick one if missing
+        id: Option<String>,
+    },
+    #[clap(about = "Unarchive a project")]
+    Unarchive {
+        /// ID of the project
+        id: String,
+    },
+    #[clap(about = "Delete a project with its sub-projects, sections and tasks")]
+    Delete {
+        /// ID of the project, pick one if missing
+        id: Option<String>,
+        /// Skip the confirmation prompt
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Sections {
+    #[clap(subcommand)]
+    command: SectionCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum SectionCommands {
+    #[clap(about = "List sections")]
+    List {
+        /// Only list sections of this project
+        #[clap(long, short)]
+        project: Option<String>,
+    },
+    #[clap(about = "Create a section")]
+    Create {
+        /// Name of the section
+        name: String,
+        /// ID of the project, prompts for it if missing
+        #[clap(long, short)]
+        project: Option<String>,
+    },
+    #[clap(about = "Rename a section")]
+    Rename {
+        /// ID of the section
+        id: String,
+        /// New name of the section
+        name: String,
+    },
+    #[clap(about = "Delete a section and its tasks")]
+    Delete {
+        /// ID of the section
+        id: String,
+        /// Skip the confirmation prompt
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Comments {
+    #[clap(subcommand)]
+    command: CommentCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum CommentCommands {
+    #[clap(about = "List the comments of a task or project")]
+    List {
+        #[clap(flatten)]
+        target: CommentTarget,
+    },
+    #[clap(about = "Add a comment to a task or project")]
+    Add {
+        /// Content of the comment, markdown is supported
+        content: String,
+        #[clap(flatten)]
+        target: CommentTarget,
+    },
+    #[clap(about = "Change the content of a comment")]
+    Edit {
+        /// ID of the comment
+        id: String,
+        /// New content of the comment
+        content: String,
+    },
+    #[clap(about = "Delete a comment")]
+    Delete {
+        /// ID of the comment
+        id: String,
+        /// Skip the confirmation prompt
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(group(ArgGroup::new("target").required(true).args(["task", "project"])))]
+struct CommentTarget {
+    /// ID of the task
+    #[clap(long, short)]
+    task: Option<String>,
+    /// ID of the project
+    #[clap(long, short)]
+    project: Option<String>,
+}
+
+impl From<&CommentTarget> for api::CommentTarget {
+    fn from(target: &CommentTarget) -> api::CommentTarget {
+        match (&target.task, &target.project) {
+            (Some(id), _) => api::CommentTarget::Task(id.to_owned()),
+            (None, Some(id)) => api::CommentTarget::Project(id.to_owned()),
+            (None, None) => unreachable!("clap requires either --task or --project"),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct Labels {
+    #[clap(subcommand)]
+    command: LabelCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum LabelCommands {
+    #[clap(about = "List labels")]
+    List {
+        /// List shared labels instead of personal ones
+        #[clap(long)]
+        shared: bool,
+    },
+    #[clap(about = "Create a personal label")]
+    Create {
+        /// Name of the label
+        name: String,
+        /// Color of the label
+        #[clap(long, short, value_parser = api::COLORS)]
+        color: Option<String>,
+        /// Mark the label as favorite
+        #[clap(long)]
+        favorite: bool,
+    },
+    #[clap(about = "Rename a label")]
+    Rename {
+        /// ID or name of the label, the name for shared labels
+        label: String,
+        /// New name of the label
+        name: String,
+        /// Rename a shared label on all tasks
+        #[clap(long)]
+        shared: bool,
+    },
+    #[clap(about = "Change the color of a label")]
+    Recolor {
+        /// ID or name of the label
+        label: String,
+        /// New color of the label
+        #[clap(value_parser = api::COLORS)]
+        color: String,
+    },
+    #[clap(about = "Add or remove a label from favorites")]
+    Favorite {
+        /// ID or name of the label
+        label: String,
+    },
+    #[clap(about = "Delete a label")]
+    Delete {
+        /// ID or name of the label, the name for shared labels
+        label: String,
+        /// Remove a shared label from all tasks
+        #[clap(long)]
+        shared: bool,
+        /// Skip the confirmation prompt
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Cache {
+    #[clap(subcommand)]
+    command: CacheCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommands {
+    #[clap(about = "Fetch projects, labels and sections again")]
+    Refresh {},
+    #[clap(about = "Remove all cached data")]
+    Clear {},
+    #[clap(about = "Show what is cached and how old it is")]
+    Status {},
+}
+
+#[derive(Debug, Args)]
+struct Config {
+    #[clap(subcommand)]
+    command: ConfigCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommands {
+    #[clap(about = "Save the API token after checking it with Todoist, same as `auth login`")]
+    Init {
+        /// API token, prompted for if not given
+        #[clap(long)]
+        token: Option<String>,
+    },
+    #[clap(about = "Print the location of the config file")]
+    Path {},
+    #[clap(about = "Print the value of a setting, e.g. `retry.max_retries`")]
+    Get {
+        /// Name of the setting
+        key: String,
+    },
+    #[clap(about = "Change a setting in the config file")]
+    Set {
+        /// Name of the setting
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Switch between the accounts in the config
+    Profiles(Profiles),
+}
+
+#[derive(Debug, Args)]
+struct Auth {
+    #[clap(subcommand)]
+    command: AuthCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum AuthCommands {
+    #[clap(about = "Save the API token in the configured secret backend after checking it")]
+    Login {
+        /// API token, prompted for if not given
+        #[clap(long)]
+        token: Option<String>,
+    },
+    #[clap(about = "Log in through the browser with a registered Todoist app")]
+    Oauth {
+        /// Client ID of the app, instead of `oauth.client_id` in the config
+        #[clap(long)]
+        client_id: Option<String>,
+        /// Client secret of the app, instead of `oauth.client_secret` in the config
+        #[clap(long)]
+        client_secret: Option<String>,
+        /// Only print the URL to log in with instead of opening it
+        #[clap(long)]
+        no_browser: bool,
+    },
+    #[clap(about = "Remove the saved API token")]
+    Logout {},
+}
+
+#[derive(Debug, Args)]
+struct Profiles {
+    #[clap(subcommand)]
+    command: ProfileCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfileCommands {
+    #[clap(about = "List the profiles, the one in use marked with *")]
+    List {},
+    #[clap(about = "Use a profile for every following command")]
+    Use {
+        /// Name of the profile
+        name: String,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -143,4 +734,16 @@ struct Cli {
     // If provided, outputs the completion file for given shell
     #[arg(long = "generate", value_enum)]
     generator: Option<Shell>,
+    /// Output format of the listing and viewing commands
+    #[arg(long, short, global = true, value_enum, default_value_t = Output::Table)]
+    output: Output,
+    /// Always fetch projects, labels and sections instead of reading the local cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+    /// Never prompt, leave optional input unset and fail if required input is missing
+    #[arg(long, global = true)]
+    no_input: bool,
+    /// Profile of the config to use, instead of `TODOIST_PROFILE` or the one set with `config profiles use`
+    #[arg(long, global = true)]
+    profile: Option<String>,
 }
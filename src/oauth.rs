@@ -0,0 +1,309 @@
+//! OAuth2 authorization code flow for logging in with a registered Todoist
+//! app instead of a personal token.
+
+use std::fmt::Display;
+use std::io;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use url::Url;
+
+pub const DEFAULT_AUTHORIZE_URL: &str = "https://todoist.com/oauth/authorize";
+pub const DEFAULT_TOKEN_URL: &str = "https://todoist.com/oauth/access_token";
+
+/// How long the flow waits for the browser to come back.
+const TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long a single connection to the listener may take to send its request.
+/// Browsers open connections ahead of time that may never send one.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Path of the loopback listener the authorization server redirects to.
+const CALLBACK_PATH: &str = "/callback";
+
+/// The Todoist app to log in with, set under `oauth` in the config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    #[serde(default = "default_authorize_url")]
+    pub authorize_url: String,
+    #[serde(default = "default_token_url")]
+    pub token_url: String,
+    /// Comma separated permissions asked for.
+    #[serde(default = "default_scope")]
+    pub scope: String,
+    /// Port of the loopback listener, any free one if 0. Needs to match the
+    /// redirect URL of the app if Todoist checks it.
+    #[serde(default)]
+    pub port: u16,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            client_id: None,
+            client_secret: None,
+            authorize_url: default_authorize_url(),
+            token_url: default_token_url(),
+            scope: default_scope(),
+            port: 0,
+        }
+    }
+}
+
+fn default_authorize_url() -> String {
+    DEFAULT_AUTHORIZE_URL.to_string()
+}
+
+fn default_token_url() -> String {
+    DEFAULT_TOKEN_URL.to_string()
+}
+
+fn default_scope() -> String {
+    String::from("data:read_write,data:delete,project:delete")
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// A setting the flow needs is missing, holds its name.
+    Missing(&'static str),
+    /// A URL setting could not be parsed, holds its name and value.
+    InvalidUrl(&'static str, String),
+    /// The listener could not be started or stopped accepting connections.
+    Io(io::Error),
+    /// The user did not log in within `TIMEOUT`.
+    Timeout,
+    /// The authorization server sent back an error instead of a code.
+    Denied(String),
+    /// The redirect did not carry the state the flow was started with.
+    State,
+    /// The token endpoint could not be reached.
+    Http(reqwest::Error),
+    /// The token endpoint refused the code.
+    Rejected { status: u16, body: String },
+}
+
+impl Error {
+    /// Exit code when a command fails with this error, see the README.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Missing(_) | Error::InvalidUrl(..) => 2,
+            Error::Denied(_) | Error::State | Error::Rejected { .. } => 3,
+            Error::Http(_) => 8,
+            Error::Io(_) | Error::Timeout => 1,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Missing(what) => write!(f, "{what} is required for logging in with OAuth"),
+            Error::InvalidUrl(what, url) => write!(f, "{what} `{url}` is not a valid URL"),
+            Error::Io(e) => write!(f, "failed to wait for the browser: {e}"),
+            Error::Timeout => write!(f, "gave up waiting for the browser"),
+            Error::Denied(reason) => write!(f, "authorization was denied: {reason}"),
+            Error::State => write!(f, "the redirect did not come from this login"),
+            Error::Http(e) => write!(f, "failed to reach the token endpoint: {e}"),
+            Error::Rejected { status, body } => {
+                write!(f, "the token endpoint rejected the code ({status}): {body}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Http(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Error {
+        Error::Http(e)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// Runs the whole flow and returns the access token. `open` is given the
+/// authorize URL to show to the user, who is sent back to a listener on
+/// 127.0.0.1 that picks up the code.
+pub async fn authorize(
+    http_client: &reqwest::Client,
+    settings: &Settings,
+    open: impl FnOnce(&Url),
+) -> Result<String, Error> {
+    let client_id = settings
+        .client_id
+        .as_deref()
+        .ok_or(Error::Missing("oauth.client_id"))?;
+    let client_secret = settings
+        .client_secret
+        .as_deref()
+        .ok_or(Error::Missing("oauth.client_secret"))?;
+
+    let token_url = Url::parse(&settings.token_url)
+        .map_err(|_| Error::InvalidUrl("oauth.token_url", settings.token_url.to_owned()))?;
+
+    let listener = TcpListener::bind(("127.0.0.1", settings.port)).await?;
+    let redirect_uri = format!(
+        "http://127.0.0.1:{}{CALLBACK_PATH}",
+        listener.local_addr()?.port()
+    );
+    let state = uuid::Uuid::new_v4().simple().to_string();
+    let url = Url::parse_with_params(
+        &settings.authorize_url,
+        [
+            ("client_id", client_id),
+            ("scope", &settings.scope),
+            ("state", &state),
+            ("redirect_uri", &redirect_uri),
+            ("response_type", "code"),
+        ],
+    )
+    .map_err(|_| Error::InvalidUrl("oauth.authorize_url", settings.authorize_url.to_owned()))?;
+    open(&url);
+
+    let code = tokio::time::timeout(TIMEOUT, wait_for_code(listener, state))
+        .await
+        .map_err(|_| Error::Timeout)??;
+
+    let response = http_client
+        .post(token_url)
+        .form(&[
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("code", &code),
+            ("redirect_uri", &redirect_uri),
+            ("grant_type", "authorization_code"),
+        ])
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::Rejected {
+            status: status.as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        });
+    }
+    Ok(response.json::<TokenResponse>().await?.access_token)
+}
+
+/// Answers requests to the listener until the redirect with the code
+/// arrives. Every connection is handled on its own, so one that never sends
+/// a request does not hold up the others.
+async fn wait_for_code(listener: TcpListener, state: String) -> Result<String, Error> {
+    let mut connections = tokio::task::JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                connections.spawn(handle(stream, state.to_owned()));
+            }
+            Some(handled) = connections.join_next() => {
+                if let Ok(Some(result)) = handled {
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+/// Answers one connection, `None` if it did not end the flow, such as a
+/// request for `/favicon.ico` or a connection that never sent anything.
+async fn handle(mut stream: TcpStream, state: String) -> Option<Result<String, Error>> {
+    let url = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream))
+        .await
+        .ok()?
+        .ok()??;
+    // Failing to answer the browser does not change the outcome of the flow.
+    if url.path() != CALLBACK_PATH {
+        let _ = respond(&mut stream, "404 Not Found", "Not found").await;
+        return None;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    if param("state") != Some(state) {
+        let _ = respond(&mut stream, "400 Bad Request", "Unknown login, try again.").await;
+        return Some(Err(Error::State));
+    }
+    if let Some(error) = param("error") {
+        let _ = respond(
+            &mut stream,
+            "200 OK",
+            "Login failed, you can close this window.",
+        )
+        .await;
+        return Some(Err(Error::Denied(error)));
+    }
+    if let Some(code) = param("code") {
+        let _ = respond(
+            &mut stream,
+            "200 OK",
+            "Logged in, you can close this window.",
+        )
+        .await;
+        return Some(Ok(code));
+    }
+    let _ = respond(
+        &mut stream,
+        "400 Bad Request",
+        "The redirect carried no code.",
+    )
+    .await;
+    None
+}
+
+/// Reads the request head and returns the requested URL, `None` if the
+/// request is not a GET.
+async fn read_request(stream: &mut TcpStream) -> io::Result<Option<Url>> {
+    let mut head = Vec::new();
+    let mut buffer = [0; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < 16 * 1024 {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => Ok(Url::parse("http://127.0.0.1")
+            .and_then(|base| base.join(target))
+            .ok()),
+        _ => Ok(None),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) -> io::Result<()> {
+    let body = format!("<!doctype html><title>todoist</title><p>{message}</p>");
+    let response = format!(
+        "HTTP/1.1 {status}\r\ncontent-type: text/html; charset=utf-8\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,363 @@
+//! Local reading of Todoist's quick add syntax. Works without the quick add
+//! endpoint, it only needs the projects, sections and labels of the account
+//! to turn a text into a task.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::api::{self, Priority};
+
+/// The parts of a quick add text, e.g. `Review PR #Work/Backend @code p2 every weekday`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuickAdd {
+    pub content: String,
+    /// Project name after `#`, the inbox if missing
+    pub project: Option<String>,
+    /// Section name after `#Project/` or a lone `/`
+    pub section: Option<String>,
+    /// Label names after `@`
+    pub labels: Vec<String>,
+    pub priority: Option<Priority>,
+    /// Due date in natural language as Todoist would read it
+    pub due: Option<String>,
+}
+
+/// Splits `text` into its parts. Projects, labels and priorities are marked
+/// in the text, the due date is guessed from date words so it can differ
+/// from what Todoist recognizes.
+pub fn parse(text: &str) -> QuickAdd {
+    let mut quick_add = QuickAdd::default();
+    let mut words = Vec::new();
+    for word in text.split_whitespace() {
+        if let Some(project) = word.strip_prefix('#').filter(|x| !x.is_empty()) {
+            let (project, section) = match project.split_once('/') {
+                Some((project, section)) => (project, Some(section)),
+                None => (project, None),
+            };
+            quick_add.project = Some(project.to_owned()).filter(|x| !x.is_empty());
+            if let Some(section) = section.filter(|x| !x.is_empty()) {
+                quick_add.section = Some(section.to_owned());
+            }
+        } else if let Some(section) = word.strip_prefix('/').filter(|x| !x.is_empty()) {
+            quick_add.section = Some(section.to_owned());
+        } else if let Some(label) = word.strip_prefix('@').filter(|x| !x.is_empty()) {
+            if !quick_add.labels.iter().any(|l| l == label) {
+                quick_add.labels.push(label.to_owned());
+            }
+        } else if let Some(priority) = priority(word) {
+            quick_add.priority = Some(priority);
+        } else {
+            words.push(word);
+        }
+    }
+
+    if let Some((start, end)) = due_phrase(&words) {
+        quick_add.due = Some(words[start..end].join(" "));
+        words.drain(start..end);
+    }
+    quick_add.content = words.join(" ");
+    quick_add
+}
+
+/// A name in a quick add text that matches nothing in the account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unknown {
+    Project(String),
+    /// A section of the named project, or of the inbox if none was named.
+    Section {
+        project: Option<String>,
+        name: String,
+    },
+    Label(String),
+}
+
+impl Display for Unknown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unknown::Project(name) => write!(f, "project \"{name}\""),
+            Unknown::Section { project, name } => write!(
+                f,
+                "section \"{name}\" in \"{}\"",
+                project.as_deref().unwrap_or("Inbox")
+            ),
+            Unknown::Label(name) => write!(f, "label \"{name}\""),
+        }
+    }
+}
+
+impl QuickAdd {
+    /// Builds the task, matching names without regard to case. Fails with
+    /// every project, section and label that does not exist.
+    pub fn resolve(
+        &self,
+        projects: &[api::Project],
+        sections: &[api::Section],
+        labels: &[api::Label],
+    ) -> Result<api::TaskCreate, Vec<Unknown>> {
+        let mut unknown = Vec::new();
+        let mut task = api::TaskCreate::new(self.content.to_owned());
+
+        let project = find_project(projects, self.project.as_deref());
+        match (&self.project, project) {
+            (Some(_), Some(project)) => {
+                task.project(project.id.to_owned());
+            }
+            (Some(name), None) => unknown.push(Unknown::Project(name.to_owned())),
+            (None, _) => {}
+        }
+
+        if let Some(name) = &self.section {
+            let section = project.and_then(|project| {
+                sections
+                    .iter()
+                    .find(|s| s.project_id == project.id && s.name.eq_ignore_ascii_case(name))
+            });
+            match section {
+                Some(section) => {
+                    task.project(section.project_id.to_owned())
+                        .section(section.id.to_owned());
+                }
+                None => unknown.push(Unknown::Section {
+                    project: self.project.to_owned(),
+                    name: name.to_owned(),
+                }),
+            }
+        }
+
+        let mut names = Vec::new();
+        for name in &self.labels {
+            match labels.iter().find(|l| l.name.eq_ignore_ascii_case(name)) {
+                Some(label) => names.push(label.name.to_owned()),
+                None => unknown.push(Unknown::Label(name.to_owned())),
+            }
+        }
+        if !names.is_empty() {
+            task.labels(names);
+        }
+
+        if let Some(priority) = self.priority {
+            task.priority(priority);
+        }
+        if let Some(due) = &self.due {
+            task.due(due.to_owned());
+        }
+
+        if unknown.is_empty() {
+            Ok(task)
+        } else {
+            Err(unknown)
+        }
+    }
+}
+
+/// Finds a project by name, or the inbox without a name.
+pub fn find_project<'a>(
+    projects: &'a [api::Project],
+    name: Option<&str>,
+) -> Option<&'a api::Project> {
+    match name {
+        Some(name) => projects.iter().find(|p| p.name.eq_ignore_ascii_case(name)),
+        None => projects.iter().find(|p| p.is_inbox_project),
+    }
+}
+
+fn priority(word: &str) -> Option<Priority> {
+    match word.to_lowercase().as_str() {
+        "p1" | "p2" | "p3" | "p4" => word.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Finds the first run of words that reads as a date, as a `start..end` range.
+fn due_phrase(words: &[&str]) -> Option<(usize, usize)> {
+    let lower = words.iter().map(|w| w.to_lowercase()).collect::<Vec<_>>();
+    let start = (0..lower.len()).find(|&i| starts_date(&lower, i))?;
+    let mut end = start + 1;
+    while end < lower.len() && extends_date(&lower, end) {
+        end += 1;
+    }
+    // Connecting words only belong to the date if something follows them.
+    while end > start + 1 && CONNECTORS.contains(&lower[end - 1].as_str()) {
+        end -= 1;
+    }
+    Some((start, end))
+}
+
+const STARTERS: [&str; 7] = [
+    "today", "tod", "tomorrow", "tom", "tonight", "everyday", "daily",
+];
+
+/// Words only part of a date if what follows is, "next week" but not
+/// "next steps".
+const REPEATS: [&str; 3] = ["next", "every", "every!"];
+
+const CONNECTORS: [&str; 8] = ["in", "at", "on", "and", "from", "until", "starting", "for"];
+
+const UNITS: [&str; 16] = [
+    "day", "days", "week", "weeks", "month", "months", "year", "years", "hour", "hours", "hr",
+    "hrs", "minute", "minutes", "min", "mins",
+];
+
+const WORDS: [&str; 13] = [
+    "other",
+    "weekday",
+    "weekdays",
+    "weekend",
+    "weekends",
+    "workday",
+    "workdays",
+    "morning",
+    "afternoon",
+    "evening",
+    "night",
+    "noon",
+    "midnight",
+];
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+fn starts_date(words: &[String], i: usize) -> bool {
+    let word = words[i].as_str();
+    let next = words.get(i + 1).map(String::as_str);
+    if STARTERS.contains(&word) || is_weekday(word) || is_date(word) || is_time(word) {
+        return true;
+    }
+    match word {
+        // "in 3 days", but not "check in with Bob"
+        "in" => next.is_some_and(is_number) && words.get(i + 2).is_some_and(|w| is_unit(w)),
+        "on" => {
+            next.is_some_and(|w| is_weekday(w) || is_date(w) || is_short_date(w))
+                || next.is_some_and(is_month) && words.get(i + 2).is_some_and(|w| is_ordinal(w))
+        }
+        "at" => next.is_some_and(is_time),
+        _ => starts_or_extends(words, i),
+    }
+}
+
+/// Whether the word at `i` continues a date that started before it.
+fn extends_date(words: &[String], i: usize) -> bool {
+    let word = words[i].as_str();
+    if REPEATS.contains(&word) || is_month(word) {
+        return starts_or_extends(words, i);
+    }
+    STARTERS.contains(&word)
+        || CONNECTORS.contains(&word)
+        || WORDS.contains(&word)
+        || is_unit(word)
+        || is_weekday(word)
+        || is_ordinal(word)
+        || is_date(word)
+        || is_time(word)
+        || words[i - 1] == "on" && is_short_date(word)
+}
+
+/// Words that need the next word to read as a date: `next` and `every`
+/// before a weekday, unit, number or the like, and a month before a day.
+fn starts_or_extends(words: &[String], i: usize) -> bool {
+    let word = words[i].as_str();
+    let Some(next) = words.get(i + 1).map(String::as_str) else {
+        return false;
+    };
+    if REPEATS.contains(&word) {
+        is_weekday(next)
+            || is_unit(next)
+            || is_ordinal(next)
+            || is_month(next)
+            || WORDS.contains(&next)
+    } else {
+        is_month(word) && is_ordinal(next)
+    }
+}
+
+fn is_unit(word: &str) -> bool {
+    UNITS.contains(&word)
+}
+
+/// Matches full names and three letter abbreviations, e.g. `fri` and `friday`.
+fn is_weekday(word: &str) -> bool {
+    WEEKDAYS
+        .iter()
+        .any(|d| word == *d || (word.starts_with(d) && word.ends_with("day")))
+}
+
+fn is_month(word: &str) -> bool {
+    const NAMES: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    MONTHS.contains(&word) || NAMES.contains(&word)
+}
+
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_ascii_digit())
+}
+
+/// `5`, `5th`, `21st`, ...
+fn is_ordinal(word: &str) -> bool {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    is_number(digits) && matches!(&word[digits.len()..], "" | "st" | "nd" | "rd" | "th")
+}
+
+/// `2024-01-31`, `31/1/2024`, `1/31/24`, ...
+fn is_date(word: &str) -> bool {
+    match date_parts(word).as_deref() {
+        Some([year, month, day]) if year.len() == 4 => is_month_day(month, day),
+        Some([a, b, year]) if matches!(year.len(), 2 | 4) => is_day_and_month(a, b),
+        _ => false,
+    }
+}
+
+/// `31/1`, `1/31`, ... only read as a date after `on`, on its own it is more
+/// likely a fraction like `3/4`.
+fn is_short_date(word: &str) -> bool {
+    matches!(date_parts(word).as_deref(), Some([a, b]) if is_day_and_month(a, b))
+}
+
+/// Splits `word` at the first `-` or `/` kind, `None` unless every part is a
+/// number.
+fn date_parts(word: &str) -> Option<Vec<&str>> {
+    let separator = word.chars().find(|c| matches!(c, '-' | '/'))?;
+    let parts = word.split(separator).collect::<Vec<_>>();
+    parts.iter().all(|p| is_number(p)).then_some(parts)
+}
+
+/// Day and month in either order, Todoist reads both.
+fn is_day_and_month(a: &str, b: &str) -> bool {
+    is_month_day(a, b) || is_month_day(b, a)
+}
+
+fn is_month_day(month: &str, day: &str) -> bool {
+    matches!(month.parse::<u32>(), Ok(1..=12)) && matches!(day.parse::<u32>(), Ok(1..=31))
+}
+
+/// `9am`, `9:30`, `17:00`, `5.30pm`, ...
+fn is_time(word: &str) -> bool {
+    let (clock, meridiem) = match word.strip_suffix("am").or(word.strip_suffix("pm")) {
+        Some(clock) => (clock, true),
+        None => (word, false),
+    };
+    // `12.50` without am or pm is a decimal, not a time.
+    let split = match meridiem {
+        true => clock.split_once([':', '.']),
+        false => clock.split_once(':'),
+    };
+    match split {
+        Some((hours, minutes)) => is_number(hours) && is_number(minutes) && minutes.len() == 2,
+        None => meridiem && is_number(clock),
+    }
+}
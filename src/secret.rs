@@ -0,0 +1,234 @@
+//! Places the API token can be kept instead of the config file.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Service name the tokens are stored under in the OS secret store.
+pub const SERVICE: &str = "todoist";
+
+/// PBKDF2 rounds deriving the key of a new secrets file from its
+/// passphrase. A file keeps the rounds it was written with.
+pub const ROUNDS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const ROUNDS_LEN: usize = 4;
+const NONCE_LEN: usize = 12;
+
+/// Where the API token is kept, set with `secret_backend` in the config.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// In plain text as `api_key` in the config file
+    #[default]
+    Config,
+    /// In the OS secret store, the Secret Service on Linux
+    Keyring,
+    /// In a file encrypted with a passphrase
+    File,
+}
+
+/// A secret store holding one token per account.
+pub enum Secrets {
+    Keyring,
+    /// Encrypted with AES-256-GCM under a key derived from `passphrase`,
+    /// with `rounds` of PBKDF2 if the file does not exist yet.
+    File {
+        path: PathBuf,
+        passphrase: String,
+        rounds: u32,
+    },
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Keyring(keyring::Error),
+    Io(io::Error),
+    /// The secrets file could not be decrypted or read.
+    Damaged,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Keyring(e) => write!(f, "secret store: {e}"),
+            Error::Io(e) => write!(f, "secrets file: {e}"),
+            Error::Damaged => write!(f, "secrets file: wrong passphrase or damaged file"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Keyring(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Damaged => None,
+        }
+    }
+}
+
+impl From<keyring::Error> for Error {
+    fn from(e: keyring::Error) -> Error {
+        Error::Keyring(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl Secrets {
+    pub fn get(&self, account: &str) -> Result<Option<String>, Error> {
+        match self {
+            Secrets::Keyring => match keyring::Entry::new(SERVICE, account)?.get_password() {
+                Ok(token) => Ok(Some(token)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+            Secrets::File {
+                path,
+                passphrase,
+                rounds,
+            } => Ok(Unlocked::read(path, passphrase, *rounds)?
+                .tokens
+                .remove(account)),
+        }
+    }
+
+    pub fn set(&self, account: &str, token: &str) -> Result<(), Error> {
+        match self {
+            Secrets::Keyring => Ok(keyring::Entry::new(SERVICE, account)?.set_password(token)?),
+            Secrets::File {
+                path,
+                passphrase,
+                rounds,
+            } => {
+                let mut file = Unlocked::read(path, passphrase, *rounds)?;
+                file.tokens.insert(account.to_owned(), token.to_owned());
+                file.write(path, passphrase)
+            }
+        }
+    }
+
+    /// Removes the token of `account`, returns whether there was one.
+    pub fn delete(&self, account: &str) -> Result<bool, Error> {
+        match self {
+            Secrets::Keyring => match keyring::Entry::new(SERVICE, account)?.delete_password() {
+                Ok(()) => Ok(true),
+                Err(keyring::Error::NoEntry) => Ok(false),
+                Err(e) => Err(e.into()),
+            },
+            Secrets::File {
+                path,
+                passphrase,
+                rounds,
+            } => {
+                let mut file = Unlocked::read(path, passphrase, *rounds)?;
+                let deleted = file.tokens.remove(account).is_some();
+                if deleted {
+                    file.write(path, passphrase)?;
+                }
+                Ok(deleted)
+            }
+        }
+    }
+}
+
+/// Decrypted secrets file. The salt, rounds and key are kept for writing it
+/// back, deriving the key is slow on purpose.
+struct Unlocked {
+    tokens: BTreeMap<String, String>,
+    salt: [u8; SALT_LEN],
+    rounds: u32,
+    cipher: Option<Aes256Gcm>,
+}
+
+impl Unlocked {
+    /// Reads the file at `path`, no tokens if there is no file yet. `rounds`
+    /// are only used for a new file.
+    fn read(path: &Path, passphrase: &str, rounds: u32) -> Result<Unlocked, Error> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mut salt = [0; SALT_LEN];
+                rand::thread_rng().fill(&mut salt);
+                return Ok(Unlocked {
+                    tokens: BTreeMap::new(),
+                    salt,
+                    rounds,
+                    cipher: None,
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if data.len() < SALT_LEN + ROUNDS_LEN + NONCE_LEN {
+            return Err(Error::Damaged);
+        }
+
+        // The file is the salt, then the rounds, then the nonce, then the
+        // encrypted JSON.
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (rounds, data) = data.split_at(ROUNDS_LEN);
+        let (nonce, encrypted) = data.split_at(NONCE_LEN);
+        let rounds = u32::from_be_bytes(rounds.try_into().map_err(|_| Error::Damaged)?);
+        if rounds == 0 {
+            return Err(Error::Damaged);
+        }
+        let cipher = cipher(passphrase, salt, rounds);
+        let json = cipher
+            .decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| Error::Damaged)?;
+        Ok(Unlocked {
+            tokens: serde_json::from_slice(&json).map_err(|_| Error::Damaged)?,
+            salt: salt.try_into().map_err(|_| Error::Damaged)?,
+            rounds,
+            cipher: Some(cipher),
+        })
+    }
+
+    /// Encrypts the tokens with a fresh nonce and replaces the file.
+    fn write(self, path: &Path, passphrase: &str) -> Result<(), Error> {
+        let mut nonce = [0; NONCE_LEN];
+        rand::thread_rng().fill(&mut nonce);
+        let cipher = self
+            .cipher
+            .unwrap_or_else(|| cipher(passphrase, &self.salt, self.rounds));
+        let json = serde_json::to_vec(&self.tokens).map_err(io::Error::from)?;
+        let encrypted = cipher
+            .encrypt(Nonce::from_slice(&nonce), json.as_slice())
+            .map_err(|_| Error::Damaged)?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // Only readable by the user, like an ssh key.
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path)?.write_all(
+            &[
+                &self.salt[..],
+                &self.rounds.to_be_bytes(),
+                &nonce,
+                &encrypted,
+            ]
+            .concat(),
+        )?;
+        Ok(())
+    }
+}
+
+fn cipher(passphrase: &str, salt: &[u8], rounds: u32) -> Aes256Gcm {
+    let mut key = [0; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    Aes256Gcm::new(&key.into())
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use std::time::Duration;
+
+use serde_json::json;
+use todoist::cache::{Kind, Store};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, label_json, project_json, section_json};
+
+async fn server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([project_json("1", "Inbox")])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([label_json("1", "work")])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/sections"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([section_json("7", "1", "Later")])),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn project_requests(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/projects")
+        .count()
+}
+
+#[tokio::test]
+async fn fresh_cache_is_read_from_disk() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+
+    store.projects(&client(&server)).await.unwrap();
+    let projects = store.projects(&client(&server)).await.unwrap();
+
+    assert_eq!(projects[0].name, "Inbox");
+    assert_eq!(project_requests(&server).await, 1);
+}
+
+#[tokio::test]
+async fn stale_cache_is_fetched_again() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::ZERO);
+
+    store.projects(&client(&server)).await.unwrap();
+    store.projects(&client(&server)).await.unwrap();
+
+    assert_eq!(project_requests(&server).await, 2);
+}
+
+#[tokio::test]
+async fn bypass_skips_reading_but_keeps_cache_warm() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+
+    Store::new(dir.path().to_path_buf(), Duration::from_secs(60))
+        .bypass()
+        .projects(&client(&server))
+        .await
+        .unwrap();
+    assert_eq!(project_requests(&server).await, 1);
+
+    Store::new(dir.path().to_path_buf(), Duration::from_secs(60))
+        .projects(&client(&server))
+        .await
+        .unwrap();
+    assert_eq!(project_requests(&server).await, 1);
+}
+
+#[tokio::test]
+async fn refresh_status_invalidate_and_clear() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().join("todoist"), Duration::from_secs(60));
+
+    assert!(store.status().is_empty());
+
+    store.refresh(&client(&server)).await.unwrap();
+    let status = store.status();
+    assert_eq!(status.len(), 3);
+    assert!(status.iter().all(|s| s.fresh && s.items == 1));
+
+    store.invalidate(Kind::Labels);
+    assert_eq!(
+        store
+            .status()
+            .iter()
+            .map(|s| s.kind.as_str())
+            .collect::<Vec<_>>(),
+        vec!["projects", "sections"]
+    );
+
+    store.clear().unwrap();
+    assert!(!store.dir().exists());
+    store.clear().unwrap();
+}
+
+#[tokio::test]
+async fn clear_leaves_other_profiles_alone() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let default = Store::new(dir.path().join("default"), Duration::from_secs(60));
+    let work = Store::new(dir.path().join("work"), Duration::from_secs(60));
+    default.refresh(&client(&server)).await.unwrap();
+    work.refresh(&client(&server)).await.unwrap();
+
+    default.clear().unwrap();
+
+    assert!(default.status().is_empty());
+    assert_eq!(work.status().len(), 3);
+}
+
+#[tokio::test]
+async fn index_resolves_cached_sections() {
+    let server = server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+
+    let index = store.index(&client(&server)).await.unwrap();
+
+    assert_eq!(index.project_name("1"), "Inbox");
+    assert_eq!(index.section_name("7"), "Later");
+}
@@ -0,0 +1,405 @@
+mod common;
+
+use std::time::Duration;
+
+use serde_json::json;
+use todoist::api;
+use todoist::cache::Store;
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, label_json, project_json, section_json, task_json};
+
+#[tokio::test]
+async fn find_sends_filter_and_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tasks"))
+        .and(query_param("filter", "today|overdue"))
+        .and(header("Authorization", "Bearer test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            task_json("1", "Buy milk"),
+            task_json("2", "Walk dog"),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tasks = client(&server)
+        .find(Some(api::TaskFilter {
+            day_filter: Some(String::from("today|overdue")),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].content, "Buy milk");
+    assert_eq!(tasks[1].id, "2");
+}
+
+#[tokio::test]
+async fn create_posts_task() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .and(body_json(json!({
+            "content": "Buy milk",
+            "due_string": "tomorrow",
+            "priority": 4,
+            "project_id": "2203306141",
+            "labels": ["work"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Buy milk")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let task = client(&server)
+        .create(
+            api::TaskCreate::new(String::from("Buy milk"))
+                .due(String::from("tomorrow"))
+                .priority(api::Priority::P1)
+                .project(String::from("2203306141"))
+                .labels(vec![String::from("work")])
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(task.id, "1");
+}
+
+#[tokio::test]
+async fn create_posts_sub_task() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .and(body_json(json!({
+            "content": "Write tests",
+            "parent_id": "1"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("2", "Write tests")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .create(
+            api::TaskCreate::new(String::from("Write tests"))
+                .parent(String::from("1"))
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn find_encodes_filter_and_project() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tasks"))
+        .and(query_param("filter", "#Work & today"))
+        .and(query_param("project_id", "2203306141"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tasks = client(&server)
+        .find(Some(api::TaskFilter {
+            day_filter: Some(String::from("#Work & today")),
+            project_id: Some(String::from("2203306141")),
+        }))
+        .await
+        .unwrap();
+
+    assert!(tasks.is_empty());
+}
+
+#[tokio::test]
+async fn close_posts_to_close_endpoint() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks/1/close"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).close("1").await.unwrap();
+}
+
+#[tokio::test]
+async fn close_fails_on_error_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks/1/close"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    assert!(matches!(
+        client(&server).close("1").await,
+        Err(api::Error::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn view_fetches_task() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tasks/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Buy milk")))
+        .mount(&server)
+        .await;
+
+    let task = client(&server).view(String::from("1")).await.unwrap();
+
+    assert_eq!(task.content, "Buy milk");
+    assert_eq!(task.due.unwrap().string, "tomorrow");
+}
+
+#[tokio::test]
+async fn project_list_fetches_projects() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            project_json("1", "Inbox"),
+            project_json("2", "Work"),
+        ])))
+        .mount(&server)
+        .await;
+
+    let projects = client(&server).project_list().await.unwrap();
+
+    assert_eq!(
+        projects.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+        vec!["Inbox", "Work"]
+    );
+}
+
+#[tokio::test]
+async fn label_list_fetches_labels() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([label_json("1", "work")])))
+        .mount(&server)
+        .await;
+
+    let labels = client(&server).label_list().await.unwrap();
+
+    assert_eq!(labels[0].name, "work");
+}
+
+#[tokio::test]
+async fn base_url_trailing_slash_is_ignored() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = api::Client::new(
+        reqwest::Client::new(),
+        common::TOKEN.to_string(),
+        format!("{}/", server.uri()),
+    );
+
+    assert!(client.label_list().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn update_sends_only_changed_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks/1"))
+        .and(body_json(json!({
+            "content": "Buy oat milk",
+            "priority": 2,
+            "duration": 15,
+            "duration_unit": "minute"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Buy oat milk")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let task = client(&server)
+        .update(
+            "1",
+            api::TaskUpdate::new()
+                .content(String::from("Buy oat milk"))
+                .priority(api::Priority::P3)
+                .duration(15, api::DurationUnit::Minute)
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(task.content, "Buy oat milk");
+}
+
+#[tokio::test]
+async fn view_reads_duration() {
+    let server = MockServer::start().await;
+    let mut task = task_json("1", "Buy milk");
+    task["duration"] = json!({"amount": 2, "unit": "day"});
+    Mock::given(method("GET"))
+        .and(path("/tasks/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task))
+        .mount(&server)
+        .await;
+
+    let task = client(&server).view(String::from("1")).await.unwrap();
+
+    assert_eq!(
+        task.duration,
+        Some(api::Duration {
+            amount: 2,
+            unit: api::DurationUnit::Day
+        })
+    );
+}
+
+#[tokio::test]
+async fn reopen_posts_to_reopen_endpoint() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks/1/reopen"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).reopen("1").await.unwrap();
+}
+
+#[tokio::test]
+async fn completed_list_reads_the_sync_api() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/sync/v9/completed/get_all"))
+        .and(header("Authorization", "Bearer test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [{
+                "id": "900",
+                "task_id": "1",
+                "project_id": "2203306141",
+                "section_id": null,
+                "content": "Buy milk",
+                "completed_at": "2023-03-01T10:00:00.000000Z",
+                "note_count": 0,
+                "meta_data": null
+            }],
+            "projects": {},
+            "sections": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let completed = client(&server)
+        .with_sync_base_url(format!("{}/sync/v9", server.uri()))
+        .completed_list()
+        .await
+        .unwrap();
+
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].task_id, "1");
+    assert_eq!(completed[0].content, "Buy milk");
+}
+
+#[tokio::test]
+async fn delete_sends_delete_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/tasks/1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).delete("1").await.unwrap();
+}
+
+#[tokio::test]
+async fn index_resolves_projects_and_sections() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            project_json("1", "Inbox"),
+            project_json("2203306141", "Work"),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/sections"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([section_json("7", "2", "Later")])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    let index = store.index(&client(&server)).await.unwrap();
+
+    assert_eq!(index.project_name("2203306141"), "Work");
+    assert_eq!(index.project_name("1"), "Inbox");
+    assert_eq!(index.project_name("404"), "404");
+    assert_eq!(index.section_name("7"), "Later");
+}
+
+#[tokio::test]
+async fn view_reads_typed_dates() {
+    let server = MockServer::start().await;
+    let mut task = task_json("1", "Buy milk");
+    task["due"]["datetime"] = json!("2016-09-01T12:00:00.000000Z");
+    task["due"]["timezone"] = json!("Europe/Riga");
+    Mock::given(method("GET"))
+        .and(path("/tasks/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task))
+        .mount(&server)
+        .await;
+
+    let task = client(&server).view(String::from("1")).await.unwrap();
+    let due = task.due.unwrap();
+
+    assert_eq!(task.created_at.to_rfc3339(), "2019-12-11T22:36:50+00:00");
+    assert_eq!(due.date.to_string(), "2016-09-01");
+    assert_eq!(
+        due.datetime.unwrap().to_rfc3339(),
+        "2016-09-01T12:00:00+00:00"
+    );
+    assert_eq!(due.timezone.as_deref(), Some("Europe/Riga"));
+}
+
+#[tokio::test]
+async fn update_sends_typed_due_date() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks/1"))
+        .and(body_json(json!({ "due_date": "2024-03-01" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Buy milk")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .update(
+            "1",
+            api::TaskUpdate::new()
+                .due_date("2024-03-01".parse().unwrap())
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use serde_json::json;
+use todoist::api;
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, comment_json};
+
+#[tokio::test]
+async fn comment_list_for_task() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/comments"))
+        .and(query_param("task_id", "1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([comment_json("9", "1", "Looks good")])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let comments = client(&server)
+        .comment_list(&api::CommentTarget::Task(String::from("1")))
+        .await
+        .unwrap();
+
+    assert_eq!(comments[0].content, "Looks good");
+}
+
+#[test]
+fn comment_target_encodes_id() {
+    assert_eq!(
+        api::CommentTarget::Task(String::from("1&project_id=2")).to_query(),
+        "?task_id=1%26project_id%3D2"
+    );
+}
+
+#[tokio::test]
+async fn comment_list_for_project() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/comments"))
+        .and(query_param("project_id", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let comments = client(&server)
+        .comment_list(&api::CommentTarget::Project(String::from("2")))
+        .await
+        .unwrap();
+
+    assert!(comments.is_empty());
+}
+
+#[tokio::test]
+async fn comment_create_only_sends_target() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/comments"))
+        .and(body_json(json!({"task_id": "1", "content": "Done?"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(comment_json("9", "1", "Done?")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let comment = client(&server)
+        .comment_create(api::CommentCreate::new(
+            String::from("Done?"),
+            api::CommentTarget::Task(String::from("1")),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(comment.task_id.as_deref(), Some("1"));
+}
+
+#[tokio::test]
+async fn comment_update_and_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/comments/9"))
+        .and(body_json(json!({"content": "Done!"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(comment_json("9", "1", "Done!")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/comments/9"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let comment = client
+        .comment_update("9", String::from("Done!"))
+        .await
+        .unwrap();
+    assert_eq!(comment.content, "Done!");
+    client.comment_delete("9").await.unwrap();
+}
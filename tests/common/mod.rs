@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use todoist::api;
+use wiremock::MockServer;
+
+pub const TOKEN: &str = "test-token";
+
+/// Returns a client pointed at the fake REST v2 server, with retries disabled.
+pub fn client(server: &MockServer) -> api::Client {
+    api::Client::new(reqwest::Client::new(), TOKEN.to_string(), server.uri())
+        .with_retry(api::RetryPolicy::none())
+}
+
+/// Retry policy with delays short enough for tests.
+pub fn fast_retry(max_retries: u32) -> api::RetryPolicy {
+    api::RetryPolicy {
+        max_retries,
+        base_delay_ms: 1,
+        max_delay_ms: 50,
+    }
+}
+
+pub fn task_json(id: &str, content: &str) -> Value {
+    json!({
+        "id": id,
+        "assigner_id": null,
+        "assignee_id": null,
+        "project_id": "2203306141",
+        "section_id": null,
+        "parent_id": null,
+        "order": 1,
+        "content": content,
+        "description": "",
+        "is_completed": false,
+        "labels": ["work"],
+        "priority": 1,
+        "comment_count": 0,
+        "creator_id": "2671355",
+        "created_at": "2019-12-11T22:36:50.000000Z",
+        "due": {
+            "date": "2016-09-01",
+            "string": "tomorrow",
+            "lang": "en",
+            "is_recurring": false
+        },
+        "url": format!("https://todoist.com/showTask?id={id}")
+    })
+}
+
+pub fn project_json(id: &str, name: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "comment_count": 0,
+        "order": 1,
+        "color": "charcoal",
+        "is_shared": false,
+        "is_favorite": false,
+        "parent_id": null,
+        "is_inbox_project": false,
+        "is_team_inbox": false,
+        "view_style": "list",
+        "url": format!("https://todoist.com/showProject?id={id}")
+    })
+}
+
+pub fn label_json(id: &str, name: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "color": "charcoal",
+        "order": 1,
+        "is_favorite": false
+    })
+}
+
+pub fn section_json(id: &str, project_id: &str, name: &str) -> Value {
+    json!({
+        "id": id,
+        "project_id": project_id,
+        "order": 1,
+        "name": name
+    })
+}
+
+pub fn comment_json(id: &str, task_id: &str, content: &str) -> Value {
+    json!({
+        "id": id,
+        "task_id": task_id,
+        "project_id": null,
+        "posted_at": "2016-09-22T07:00:00.000000Z",
+        "content": content,
+        "attachment": null
+    })
+}
@@ -0,0 +1,354 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::json;
+use todoist::cache::Store;
+use todoist::cmd::{self, output::Output, Cmd, Prompt};
+use todoist::config::{self, Vars};
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, project_json, task_json};
+
+const CONFIG: &str = "
+api_key: top-token
+base_url: http://localhost:8080
+default_filter: today
+profiles:
+  work:
+    api_key: work-token
+    default_project: Work
+  personal:
+    api_key: personal-token
+    base_url: http://localhost:9090
+";
+
+fn write_config(dir: &Path, content: &str) -> std::path::PathBuf {
+    let path = dir.join("config.yaml");
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn load_without_profile_uses_top_level_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = config::load(&write_config(dir.path(), CONFIG), None, &Vars::new()).unwrap();
+
+    assert_eq!(config.api_key, "top-token");
+    assert_eq!(config.profile, None);
+    assert_eq!(config.profiles.len(), 2);
+}
+
+#[test]
+fn load_with_profile_replaces_top_level_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = config::load(
+        &write_config(dir.path(), CONFIG),
+        Some("personal"),
+        &Vars::new(),
+    )
+    .unwrap();
+
+    assert_eq!(config.api_key, "personal-token");
+    assert_eq!(config.base_url, "http://localhost:9090");
+    assert_eq!(config.default_filter.as_deref(), Some("today"));
+    assert_eq!(config.profile.as_deref(), Some("personal"));
+}
+
+#[test]
+fn load_uses_profile_selected_in_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), &format!("profile: work\n{CONFIG}"));
+
+    let config = config::load(&path, None, &Vars::new()).unwrap();
+    assert_eq!(config.api_key, "work-token");
+    assert_eq!(config.default_project.as_deref(), Some("Work"));
+
+    // An explicit profile wins over the one in the file.
+    let config = config::load(&path, Some("personal"), &Vars::new()).unwrap();
+    assert_eq!(config.api_key, "personal-token");
+}
+
+#[test]
+fn load_lets_variables_replace_file_and_profile_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let vars = Vars::new()
+        .with("TODOIST_API_KEY", "env-token")
+        .with("TODOIST_CACHE_TTL", "5")
+        .with("TODOIST_UNKNOWN", "ignored");
+
+    let config = config::load(&write_config(dir.path(), CONFIG), Some("personal"), &vars).unwrap();
+
+    assert_eq!(config.api_key, "env-token");
+    assert_eq!(config.cache_ttl, 5);
+    assert_eq!(config.base_url, "http://localhost:9090");
+}
+
+#[test]
+fn load_keeps_text_variables_that_look_like_numbers() {
+    let dir = tempfile::tempdir().unwrap();
+    let vars = Vars::new()
+        .with("TODOIST_API_KEY", "0123456789")
+        .with("TODOIST_DEFAULT_PROJECT", "2024")
+        .with("TODOIST_DEFAULT_FILTER", "true");
+
+    let config = config::load(&write_config(dir.path(), CONFIG), None, &vars).unwrap();
+
+    assert_eq!(config.api_key, "0123456789");
+    assert_eq!(config.default_project.as_deref(), Some("2024"));
+    assert_eq!(config.default_filter.as_deref(), Some("true"));
+}
+
+#[test]
+fn load_rejects_profile_named_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), "profiles:\n  default:\n    api_key: token\n");
+
+    assert!(config::load(&path, None, &Vars::new()).is_err());
+    assert!(config::use_profile(&path, "default").is_err());
+}
+
+#[test]
+fn load_rejects_unknown_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = config::load(
+        &write_config(dir.path(), CONFIG),
+        Some("home"),
+        &Vars::new(),
+    )
+    .err()
+    .unwrap();
+
+    assert_eq!(err.to_string(), "unknown profile `home`");
+}
+
+#[test]
+fn use_profile_selects_it_in_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), CONFIG);
+
+    config::use_profile(&path, "work").unwrap();
+    assert_eq!(
+        config::Profiles::read(&path).unwrap().active.as_deref(),
+        Some("work")
+    );
+    assert_eq!(
+        config::load(&path, None, &Vars::new()).unwrap().api_key,
+        "work-token"
+    );
+
+    assert!(config::use_profile(&path, "home").is_err());
+    assert_eq!(
+        config::Profiles::read(&path).unwrap().active.as_deref(),
+        Some("work")
+    );
+}
+
+#[tokio::test]
+async fn default_filter_is_used_without_filter() {
+    let server = MockServer::start().await;
+    for route in ["/projects", "/sections"] {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/tasks"))
+        .and(query_param("filter", "#Work"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    Cmd::new(&client, &store, Output::Json, Prompt::new(false))
+        .with_default_filter(Some(String::from("#Work")))
+        .tasks
+        .list(&None, Default::default(), None, &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn default_project_is_used_without_project() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([project_json("7", "Work")])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .and(body_json(json!({"content": "Deploy", "project_id": "7"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Deploy")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    Cmd::new(&client, &store, Output::Table, Prompt::new(false))
+        .with_default_project(Some(String::from("work")))
+        .tasks
+        .create(
+            &Some(String::from("Deploy")),
+            &None,
+            &None,
+            &None,
+            &None,
+            &vec![],
+            &None,
+        )
+        .await
+        .unwrap();
+}
+
+#[test]
+fn migrate_moves_legacy_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let legacy = dir.path().join("api/config.yaml");
+    fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+    fs::write(&legacy, CONFIG).unwrap();
+    let path = dir.path().join("todoist/config.yaml");
+
+    assert!(config::migrate(&legacy, &path).unwrap());
+    assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG);
+    assert!(!legacy.parent().unwrap().exists());
+
+    // Nothing left to move the second time.
+    assert!(!config::migrate(&legacy, &path).unwrap());
+}
+
+#[test]
+fn migrate_keeps_existing_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let legacy = write_config(dir.path(), CONFIG);
+    let path = dir.path().join("todoist/config.yaml");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "api_key: new-token\n").unwrap();
+
+    assert!(!config::migrate(&legacy, &path).unwrap());
+    assert!(legacy.exists());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "api_key: new-token\n");
+}
+
+#[test]
+fn set_creates_the_file_and_nested_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+
+    config::set(&path, None, "api_key", "token").unwrap();
+    config::set(&path, None, "retry.max_retries", "5").unwrap();
+
+    let config = config::load(&path, None, &Vars::new()).unwrap();
+    assert_eq!(config.api_key, "token");
+    assert_eq!(config.retry.max_retries, 5);
+    assert_eq!(
+        config::get(&path, None, "retry.max_retries", &Vars::new())
+            .unwrap()
+            .as_deref(),
+        Some("5")
+    );
+    assert_eq!(
+        config::get(&path, None, "default_filter", &Vars::new()).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn get_falls_back_to_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), "api_key: token\n");
+    let get = |key| config::get(&path, None, key, &Vars::new()).unwrap();
+
+    assert_eq!(
+        get("base_url").as_deref(),
+        Some("https://api.todoist.com/rest/v2")
+    );
+    assert_eq!(get("retry.max_retries").as_deref(), Some("3"));
+    assert_eq!(get("cache_ttl").as_deref(), Some("600"));
+}
+
+#[test]
+fn set_writes_into_the_selected_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), CONFIG);
+
+    config::set(&path, Some("work"), "default_filter", "#Work").unwrap();
+
+    assert_eq!(
+        config::get(&path, Some("work"), "default_filter", &Vars::new())
+            .unwrap()
+            .as_deref(),
+        Some("#Work")
+    );
+    assert_eq!(
+        config::get(&path, None, "default_filter", &Vars::new())
+            .unwrap()
+            .as_deref(),
+        Some("today")
+    );
+}
+
+#[test]
+fn set_rejects_unknown_keys_and_bad_numbers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), CONFIG);
+
+    assert!(config::set(&path, None, "colour", "red").is_err());
+    assert!(config::set(&path, None, "cache_ttl", "soon").is_err());
+    assert!(config::set(&path, None, "oauth.port", "any").is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG);
+}
+
+#[tokio::test]
+async fn init_saves_a_token_todoist_accepts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .and(header("Authorization", "Bearer new-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), &format!("base_url: {}\n", server.uri()));
+
+    cmd::Auth::new(path.to_owned(), None, Vars::new(), Prompt::new(false))
+        .login(&Some(String::from("new-token")))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        config::load(&path, None, &Vars::new()).unwrap().api_key,
+        "new-token"
+    );
+}
+
+#[tokio::test]
+async fn init_keeps_config_when_token_is_rejected() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), &format!("base_url: {}\n", server.uri()));
+
+    let err = cmd::Auth::new(path.to_owned(), None, Vars::new(), Prompt::new(false))
+        .login(&Some(String::from("bad-token")))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.exit_code(), 3);
+    assert_eq!(config::load(&path, None, &Vars::new()).unwrap().api_key, "");
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use std::time::Duration;
+
+use todoist::api;
+use todoist::cache::Store;
+use todoist::cmd::{self, output::Output};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::client;
+
+async fn labels_with_response(response: ResponseTemplate) -> api::Result<Vec<api::Label>> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+
+    client(&server).label_list().await
+}
+
+#[tokio::test]
+async fn unauthorized_carries_body() {
+    let err = labels_with_response(ResponseTemplate::new(401).set_body_string("Forbidden"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::Unauthorized { .. }));
+    assert_eq!(err.body(), Some("Forbidden"));
+    assert!(err.to_string().contains("todoist auth login"));
+}
+
+#[tokio::test]
+async fn not_found_is_not_a_decode_error() {
+    let err = labels_with_response(ResponseTemplate::new(404).set_body_string("Task not found"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn rate_limited_reads_retry_after() {
+    let err = labels_with_response(ResponseTemplate::new(429).insert_header("Retry-After", "30"))
+        .await
+        .unwrap_err();
+
+    match err {
+        api::Error::RateLimited { retry_after, .. } => {
+            assert_eq!(retry_after, Some(Duration::from_secs(30)))
+        }
+        e => panic!("unexpected error {e:?}"),
+    }
+}
+
+#[tokio::test]
+async fn server_error_keeps_status() {
+    let err = labels_with_response(ResponseTemplate::new(503))
+        .await
+        .unwrap_err();
+
+    match err {
+        api::Error::Server { status, .. } => assert_eq!(status.as_u16(), 503),
+        e => panic!("unexpected error {e:?}"),
+    }
+}
+
+#[tokio::test]
+async fn bad_request_is_a_request_error() {
+    let err = labels_with_response(ResponseTemplate::new(400).set_body_string("Invalid argument"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::Request { .. }));
+    assert!(err.to_string().ends_with(": Invalid argument"));
+}
+
+#[tokio::test]
+async fn malformed_body_is_a_decode_error() {
+    let err = labels_with_response(ResponseTemplate::new(200).set_body_string("not json"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::Decode { .. }));
+    assert_eq!(err.body(), Some("not json"));
+}
+
+#[tokio::test]
+async fn exit_codes_are_distinct() {
+    let codes = [
+        labels_with_response(ResponseTemplate::new(401)).await,
+        labels_with_response(ResponseTemplate::new(404)).await,
+        labels_with_response(ResponseTemplate::new(429)).await,
+        labels_with_response(ResponseTemplate::new(500)).await,
+        labels_with_response(ResponseTemplate::new(400)).await,
+        labels_with_response(ResponseTemplate::new(200)).await,
+    ]
+    .into_iter()
+    .map(|r| r.unwrap_err().exit_code())
+    .collect::<std::collections::HashSet<_>>();
+
+    assert_eq!(codes.len(), 6);
+}
+
+#[tokio::test]
+async fn command_errors_keep_api_exit_codes() {
+    let err: cmd::Error = labels_with_response(ResponseTemplate::new(404))
+        .await
+        .unwrap_err()
+        .into();
+
+    assert_eq!(err.exit_code(), 4);
+    assert_eq!(err.to_string(), "the requested resource was not found");
+}
+
+#[test]
+fn command_error_exit_codes() {
+    assert_eq!(cmd::Error::Cancelled.exit_code(), 130);
+    assert_eq!(
+        cmd::Error::MissingInput(String::from("--yes")).exit_code(),
+        2
+    );
+    assert_eq!(
+        cmd::Error::NothingToPick(String::from("Task")).exit_code(),
+        4
+    );
+    assert_eq!(
+        cmd::Error::from(std::io::Error::other("disk full")).exit_code(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn failed_api_call_is_returned_from_command() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tasks/1"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(&server);
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    let cmd = cmd::Cmd::new(&client, &store, Output::Table, cmd::Prompt::new(false));
+
+    let err = cmd.tasks.view("1").await.unwrap_err();
+
+    assert_eq!(err.exit_code(), 3);
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use serde_json::json;
+use todoist::api;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, label_json};
+
+#[tokio::test]
+async fn label_create_sends_options() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/labels"))
+        .and(body_json(
+            json!({"name": "errands", "color": "red", "is_favorite": true}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(label_json("1", "errands")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let label = client(&server)
+        .label_create(
+            api::LabelCreate::new(String::from("errands"))
+                .color(String::from("red"))
+                .favorite(true)
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(label.name, "errands");
+}
+
+#[tokio::test]
+async fn label_update_sends_only_changes() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/labels/1"))
+        .and(body_json(json!({"color": "blue"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(label_json("1", "errands")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .label_update(
+            "1",
+            api::LabelUpdate::new()
+                .color(String::from("blue"))
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn label_view_and_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(label_json("1", "errands")))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/labels/1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    assert_eq!(client.label_view("1").await.unwrap().name, "errands");
+    client.label_delete("1").await.unwrap();
+}
+
+#[tokio::test]
+async fn shared_labels() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels/shared"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!(["team", "urgent"])))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/labels/shared/rename"))
+        .and(body_json(json!({"name": "team", "new_name": "squad"})))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/labels/shared/remove"))
+        .and(body_json(json!({"name": "urgent"})))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    assert_eq!(
+        client.shared_label_list().await.unwrap(),
+        vec!["team", "urgent"]
+    );
+    client.shared_label_rename("team", "squad").await.unwrap();
+    client.shared_label_remove("urgent").await.unwrap();
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use todoist::api::{self, Index, Task};
+use todoist::cmd::layout::{group, sort, GroupBy, Sort};
+
+use common::{project_json, task_json};
+
+fn task(id: &str, project: &str, priority: i64, labels: &[&str]) -> Task {
+    let mut json = task_json(id, id);
+    json["project_id"] = project.into();
+    json["priority"] = priority.into();
+    json["labels"] = labels.to_vec().into();
+    serde_json::from_value(json).unwrap()
+}
+
+fn index() -> Index {
+    let projects: Vec<api::Project> = serde_json::from_value(serde_json::json!([
+        project_json("1", "Work"),
+        project_json("2", "Home"),
+    ]))
+    .unwrap();
+    Index::new(projects, Vec::new())
+}
+
+fn ids(tasks: &[&Task]) -> Vec<String> {
+    tasks.iter().map(|t| t.id.to_owned()).collect()
+}
+
+#[test]
+fn sort_by_priority_puts_most_urgent_first() {
+    let mut tasks = vec![
+        task("a", "1", 1, &[]),
+        task("b", "1", 4, &[]),
+        task("c", "1", 2, &[]),
+    ];
+
+    sort(&mut tasks, Sort::Priority, &index());
+
+    assert_eq!(ids(&tasks.iter().collect::<Vec<_>>()), ["b", "c", "a"]);
+}
+
+#[test]
+fn sort_by_project_uses_project_names() {
+    let mut tasks = vec![task("a", "1", 1, &[]), task("b", "2", 1, &[])];
+
+    sort(&mut tasks, Sort::Project, &index());
+
+    assert_eq!(ids(&tasks.iter().collect::<Vec<_>>()), ["b", "a"]);
+}
+
+#[test]
+fn group_by_project_is_ordered_by_name() {
+    let tasks = vec![
+        task("a", "1", 1, &[]),
+        task("b", "2", 1, &[]),
+        task("c", "1", 1, &[]),
+    ];
+
+    let groups = group(&tasks, GroupBy::Project, &index());
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].0, "Home");
+    assert_eq!(ids(&groups[0].1), ["b"]);
+    assert_eq!(groups[1].0, "Work");
+    assert_eq!(ids(&groups[1].1), ["a", "c"]);
+}
+
+#[test]
+fn group_by_label_repeats_tasks_and_puts_unlabeled_last() {
+    let tasks = vec![
+        task("a", "1", 1, &[]),
+        task("b", "1", 1, &["work", "errand"]),
+        task("c", "1", 1, &["work"]),
+    ];
+
+    let groups = group(&tasks, GroupBy::Label, &index());
+
+    let names = groups
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["errand", "work", "No label"]);
+    assert_eq!(ids(&groups[1].1), ["b", "c"]);
+    assert_eq!(ids(&groups[2].1), ["a"]);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use std::time::Duration;
+
+use serde_json::json;
+use todoist::cache::Store;
+use todoist::cmd::{output::Output, Cmd, Error, Prompt};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, task_json};
+
+#[test]
+fn optional_input_is_left_unset() {
+    let prompt = Prompt::new(false);
+
+    assert_eq!(prompt.optional_input::<String>("Due date").unwrap(), None);
+    assert_eq!(prompt.confirm("Favorite?", true).unwrap(), None);
+    assert_eq!(prompt.select("Color:", &["red"], 0).unwrap(), None);
+}
+
+#[test]
+fn required_input_fails_with_usage_exit_code() {
+    let err = Prompt::new(false).require("--project").unwrap_err();
+
+    assert!(matches!(err, Error::MissingInput(ref what) if what == "--project"));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn destructive_actions_need_yes() {
+    let prompt = Prompt::new(false);
+
+    assert!(prompt.confirm_destructive("Delete?", true).is_ok());
+    assert!(prompt.confirm_destructive("Delete?", false).is_err());
+}
+
+#[tokio::test]
+async fn create_sends_only_given_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .and(body_json(json!({ "content": "Buy milk" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Buy milk")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(&server);
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    let cmd = Cmd::new(&client, &store, Output::Table, Prompt::new(false));
+
+    cmd.tasks
+        .create(
+            &Some(String::from("Buy milk")),
+            &None,
+            &None,
+            &None,
+            &None,
+            &Vec::new(),
+            &None,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn create_without_content_fails() {
+    let server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(&server);
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    let cmd = Cmd::new(&client, &store, Output::Table, Prompt::new(false));
+
+    let err = cmd
+        .tasks
+        .create(&None, &None, &None, &None, &None, &Vec::new(), &None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::MissingInput(_)));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
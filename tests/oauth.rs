@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use todoist::oauth::{self, Error, Settings};
+use url::Url;
+use wiremock::matchers::{body_string_contains, method, path, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn settings(server: &MockServer) -> Settings {
+    Settings {
+        client_id: Some("client-id".to_string()),
+        client_secret: Some("client-secret".to_string()),
+        authorize_url: format!("{}/oauth/authorize", server.uri()),
+        token_url: format!("{}/oauth/access_token", server.uri()),
+        ..Settings::default()
+    }
+}
+
+/// Fake authorize endpoint that sends the browser back with `params` and the
+/// state it was given.
+async fn mount_authorize(server: &MockServer, params: &'static str) {
+    Mock::given(method("GET"))
+        .and(path("/oauth/authorize"))
+        .and(query_param("client_id", "client-id"))
+        .and(query_param("response_type", "code"))
+        .respond_with(move |request: &Request| {
+            let query = |name: &str| {
+                request
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.into_owned())
+                    .unwrap()
+            };
+            let location = format!(
+                "{}?{params}&state={}",
+                query("redirect_uri"),
+                query("state")
+            );
+            ResponseTemplate::new(302).insert_header("location", location.as_str())
+        })
+        .mount(server)
+        .await;
+}
+
+/// Follows the authorize URL like a browser would, redirects included.
+fn browser(url: &Url) {
+    let url = url.clone();
+    tokio::spawn(async move { reqwest::get(url).await });
+}
+
+#[tokio::test]
+async fn authorize_exchanges_code_for_token() {
+    let server = MockServer::start().await;
+    mount_authorize(&server, "code=the-code").await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/access_token"))
+        .and(body_string_contains("code=the-code"))
+        .and(body_string_contains("client_secret=client-secret"))
+        .and(body_string_contains("grant_type=authorization_code"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "oauth-token",
+            "token_type": "Bearer",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let token = oauth::authorize(&reqwest::Client::new(), &settings(&server), browser)
+        .await
+        .unwrap();
+
+    assert_eq!(token, "oauth-token");
+}
+
+#[tokio::test]
+async fn authorize_is_not_held_up_by_idle_connections() {
+    let server = MockServer::start().await;
+    mount_authorize(&server, "code=the-code").await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/access_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "oauth-token",
+            "token_type": "Bearer",
+        })))
+        .mount(&server)
+        .await;
+
+    // Like a browser opening a connection ahead of time that it never uses.
+    let open = |url: &Url| {
+        let url = url.clone();
+        let redirect = url
+            .query_pairs()
+            .find(|(key, _)| key == "redirect_uri")
+            .map(|(_, value)| Url::parse(&value).unwrap())
+            .unwrap();
+        tokio::spawn(async move {
+            let idle = tokio::net::TcpStream::connect(("127.0.0.1", redirect.port().unwrap()))
+                .await
+                .unwrap();
+            let _ = reqwest::get(url).await;
+            drop(idle);
+        });
+    };
+    let token = tokio::time::timeout(
+        Duration::from_secs(3),
+        oauth::authorize(&reqwest::Client::new(), &settings(&server), open),
+    )
+    .await
+    .expect("the idle connection held up the login")
+    .unwrap();
+
+    assert_eq!(token, "oauth-token");
+}
+
+#[tokio::test]
+async fn authorize_fails_when_access_is_denied() {
+    let server = MockServer::start().await;
+    mount_authorize(&server, "error=access_denied").await;
+
+    let err = oauth::authorize(&reqwest::Client::new(), &settings(&server), browser)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(&err, Error::Denied(reason) if reason == "access_denied"));
+    assert_eq!(err.exit_code(), 3);
+}
+
+#[tokio::test]
+async fn authorize_fails_when_token_endpoint_rejects_code() {
+    let server = MockServer::start().await;
+    mount_authorize(&server, "code=the-code").await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/access_token"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("bad_authorization_code"))
+        .mount(&server)
+        .await;
+
+    let err = oauth::authorize(&reqwest::Client::new(), &settings(&server), browser)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::Rejected { status: 400, .. }));
+    assert!(err.to_string().contains("bad_authorization_code"));
+}
+
+#[tokio::test]
+async fn authorize_requires_client_id() {
+    let server = MockServer::start().await;
+    let settings = Settings {
+        client_id: None,
+        ..settings(&server)
+    };
+
+    let err = oauth::authorize(&reqwest::Client::new(), &settings, |_| {
+        panic!("nothing to open without a client ID")
+    })
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, Error::Missing("oauth.client_id")));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[tokio::test]
+async fn authorize_rejects_invalid_urls() {
+    let server = MockServer::start().await;
+    let settings = Settings {
+        authorize_url: String::from("not a url"),
+        ..settings(&server)
+    };
+
+    let err = oauth::authorize(&reqwest::Client::new(), &settings, |_| {
+        panic!("nothing to open without a valid URL")
+    })
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, Error::InvalidUrl("oauth.authorize_url", _)));
+    assert_eq!(
+        err.to_string(),
+        "oauth.authorize_url `not a url` is not a valid URL"
+    );
+}
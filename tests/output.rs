@@ -0,0 +1,86 @@
+mod common;
+
+use serde_json::json;
+use todoist::api;
+use todoist::cmd::output::{write_item, write_items, Output};
+
+use common::{project_json, task_json};
+
+fn tasks() -> Vec<api::Task> {
+    let mut without_due = task_json("2", "Call \"mom\", dad");
+    without_due["due"] = json!(null);
+    vec![
+        serde_json::from_value(task_json("1", "Buy milk")).unwrap(),
+        serde_json::from_value(without_due).unwrap(),
+    ]
+}
+
+fn render(format: Output) -> String {
+    let mut out = Vec::new();
+    write_items(format, &tasks(), &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn csv_quotes_commas_and_quotes() {
+    let csv = render(Output::Csv);
+    let lines = csv.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id,assigner_id,assignee_id,project_id"));
+    assert!(lines[2].contains(r#""Call ""mom"", dad""#));
+    assert!(!lines[0].ends_with(','));
+}
+
+#[test]
+fn csv_flattens_nested_fields_in_place() {
+    let csv = render(Output::Csv);
+    let header = csv.lines().next().unwrap().split(',').collect::<Vec<_>>();
+
+    let due = header.iter().position(|h| *h == "due.date").unwrap();
+    assert_eq!(header[due - 1], "created_at");
+    assert!(!header.contains(&"due"));
+    assert!(header.contains(&"labels"));
+}
+
+#[test]
+fn tsv_uses_tabs() {
+    let tsv = render(Output::Tsv);
+
+    assert!(tsv.lines().next().unwrap().starts_with("id\tassigner_id"));
+}
+
+#[test]
+fn json_is_a_single_array() {
+    let value: serde_json::Value = serde_json::from_str(&render(Output::Json)).unwrap();
+
+    assert_eq!(value.as_array().unwrap().len(), 2);
+    assert_eq!(value[0]["content"], "Buy milk");
+}
+
+#[test]
+fn ndjson_is_one_task_per_line() {
+    let ndjson = render(Output::Ndjson);
+    let lines = ndjson.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 2);
+    let task: api::Task = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(task.id, "2");
+}
+
+#[test]
+fn yaml_round_trips() {
+    let tasks: Vec<api::Task> = serde_yaml::from_str(&render(Output::Yaml)).unwrap();
+
+    assert_eq!(tasks[0].content, "Buy milk");
+}
+
+#[test]
+fn single_item_is_not_wrapped() {
+    let project: api::Project = serde_json::from_value(project_json("1", "Inbox")).unwrap();
+    let mut out = Vec::new();
+    write_item(Output::Json, &project, &mut out).unwrap();
+
+    let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(value["name"], "Inbox");
+}
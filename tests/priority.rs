@@ -0,0 +1,43 @@
+use serde_json::json;
+use todoist::api::Priority;
+
+#[test]
+fn api_numbers_are_inverted() {
+    assert_eq!(serde_json::to_value(Priority::P1).unwrap(), json!(4));
+    assert_eq!(serde_json::to_value(Priority::P4).unwrap(), json!(1));
+    assert_eq!(
+        serde_json::from_value::<Priority>(json!(3)).unwrap(),
+        Priority::P2
+    );
+}
+
+#[test]
+fn out_of_range_api_number_is_rejected() {
+    assert!(serde_json::from_value::<Priority>(json!(5)).is_err());
+    assert!(serde_json::from_value::<Priority>(json!(0)).is_err());
+}
+
+#[test]
+fn parses_app_notation_and_names() {
+    for input in ["p1", "P1", "1", "urgent", " Urgent "] {
+        assert_eq!(input.parse::<Priority>(), Ok(Priority::P1), "{input}");
+    }
+    assert_eq!("high".parse::<Priority>(), Ok(Priority::P2));
+    assert_eq!("3".parse::<Priority>(), Ok(Priority::P3));
+    assert_eq!("low".parse::<Priority>(), Ok(Priority::P4));
+    assert!("p5".parse::<Priority>().is_err());
+    assert!("".parse::<Priority>().is_err());
+}
+
+#[test]
+fn displays_like_the_app() {
+    assert_eq!(Priority::P1.to_string(), "p1");
+    assert!(Priority::P1 < Priority::P4);
+}
+
+#[test]
+fn flag_is_colored_except_for_p4() {
+    assert_eq!(Priority::P2.flag_label(), "⚑ p2");
+    assert!(Priority::P1.rgb().is_some());
+    assert_eq!(Priority::P4.rgb(), None);
+}
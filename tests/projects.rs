@@ -0,0 +1,144 @@
+mod common;
+
+use serde_json::json;
+use todoist::api;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{client, project_json};
+
+#[tokio::test]
+async fn project_create_sends_options() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/projects"))
+        .and(body_json(json!({
+            "name": "Backend",
+            "parent_id": "1",
+            "color": "blue",
+            "is_favorite": false,
+            "view_style": "board"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(project_json("2", "Backend")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let project = client(&server)
+        .project_create(
+            api::ProjectCreate::new(String::from("Backend"))
+                .parent(String::from("1"))
+                .color(String::from("blue"))
+                .favorite(false)
+                .view_style(String::from("board"))
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(project.id, "2");
+}
+
+#[tokio::test]
+async fn project_update_sends_only_changes() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/projects/2"))
+        .and(body_json(json!({"name": "Platform", "is_favorite": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(project_json("2", "Platform")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let project = client(&server)
+        .project_update(
+            "2",
+            api::ProjectUpdate::new()
+                .name(String::from("Platform"))
+                .favorite(true)
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(project.name, "Platform");
+}
+
+/// Fake Sync API answering the single command it gets with `status`, after
+/// checking its type and arguments.
+async fn mount_sync(server: &MockServer, args: serde_json::Value, status: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path("/sync/v9/sync"))
+        .respond_with(move |request: &Request| {
+            let body: serde_json::Value = request.body_json().unwrap();
+            let command = &body["commands"][0];
+            assert_eq!(command["type"], "project_move");
+            assert_eq!(command["args"], args);
+            let uuid = command["uuid"].as_str().unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "sync_status": { uuid: status.clone() }
+            }))
+        })
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn project_move_sends_sync_command() {
+    let server = MockServer::start().await;
+    mount_sync(&server, json!({"id": "2", "parent_id": "1"}), json!("ok")).await;
+
+    client(&server)
+        .with_sync_base_url(format!("{}/sync/v9", server.uri()))
+        .project_move("2", Some("1"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn project_move_to_top_level_and_refused_command() {
+    let server = MockServer::start().await;
+    mount_sync(
+        &server,
+        json!({"id": "2", "parent_id": null}),
+        json!({"error": "Project not found", "error_code": 22, "http_code": 404}),
+    )
+    .await;
+
+    let err = client(&server)
+        .with_sync_base_url(format!("{}/sync/v9", server.uri()))
+        .project_move("2", None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::NotFound { ref body } if body == "Project not found"));
+}
+
+#[tokio::test]
+async fn project_archive_unarchive_and_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/projects/2/archive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(project_json("2", "Backend")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/projects/2/unarchive"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/projects/2"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    client.project_archive("2").await.unwrap();
+    client.project_unarchive("2").await.unwrap();
+    client.project_delete("2").await.unwrap();
+}
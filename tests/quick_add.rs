@@ -0,0 +1,312 @@
+mod common;
+
+use std::time::Duration;
+
+use serde_json::json;
+use todoist::api;
+use todoist::cache::Store;
+use todoist::cmd::{output::Output, Cmd, Error, Prompt};
+use todoist::quick_add::{parse, QuickAdd, Unknown};
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, label_json, project_json, section_json, task_json, TOKEN};
+
+fn projects() -> Vec<api::Project> {
+    let mut inbox = project_json("1", "Inbox");
+    inbox["is_inbox_project"] = json!(true);
+    serde_json::from_value(json!([inbox, project_json("2", "Work")])).unwrap()
+}
+
+fn sections() -> Vec<api::Section> {
+    serde_json::from_value(json!([
+        section_json("10", "2", "Backend"),
+        section_json("11", "1", "Errands")
+    ]))
+    .unwrap()
+}
+
+fn labels() -> Vec<api::Label> {
+    serde_json::from_value(json!([label_json("20", "Code")])).unwrap()
+}
+
+#[test]
+fn parse_reads_project_labels_priority_and_due() {
+    assert_eq!(
+        parse("Pay rent tomorrow 9am #Home @finance p1"),
+        QuickAdd {
+            content: String::from("Pay rent"),
+            project: Some(String::from("Home")),
+            section: None,
+            labels: vec![String::from("finance")],
+            priority: Some(api::Priority::P1),
+            due: Some(String::from("tomorrow 9am")),
+        }
+    );
+}
+
+#[test]
+fn parse_reads_recurring_and_relative_dates() {
+    assert_eq!(
+        parse("Stand-up every weekday at 10:00").due.as_deref(),
+        Some("every weekday at 10:00")
+    );
+    assert_eq!(
+        parse("Renew passport in 3 weeks").due.as_deref(),
+        Some("in 3 weeks")
+    );
+    assert_eq!(
+        parse("Dentist on friday 4pm").due.as_deref(),
+        Some("on friday 4pm")
+    );
+    assert_eq!(parse("Taxes april 15th").due.as_deref(), Some("april 15th"));
+}
+
+#[test]
+fn parse_leaves_plain_words_in_the_content() {
+    let quick_add = parse("Check in with Bob at the office");
+    assert_eq!(quick_add.content, "Check in with Bob at the office");
+    assert_eq!(quick_add.due, None);
+    assert_eq!(quick_add.project, None);
+    assert_eq!(quick_add.priority, None);
+}
+
+#[test]
+fn parse_reads_numeric_dates() {
+    assert_eq!(
+        parse("Submit report 2024-01-31").due.as_deref(),
+        Some("2024-01-31")
+    );
+    assert_eq!(
+        parse("Pay invoice 31/1/2024").due.as_deref(),
+        Some("31/1/2024")
+    );
+    assert_eq!(parse("Call mom on 3/4").due.as_deref(), Some("on 3/4"));
+}
+
+#[test]
+fn parse_leaves_decimals_and_fractions_in_the_content() {
+    for text in [
+        "Buy 1.5 kg flour",
+        "Cut 3/4 of the board",
+        "Pay 12.50 for lunch",
+        "Replace 10-32 screws",
+    ] {
+        let quick_add = parse(text);
+        assert_eq!(quick_add.content, text);
+        assert_eq!(quick_add.due, None);
+    }
+}
+
+#[test]
+fn parse_leaves_common_words_in_the_content() {
+    for text in ["Plan next steps", "Check every box", "Ask may I join"] {
+        let quick_add = parse(text);
+        assert_eq!(quick_add.content, text);
+        assert_eq!(quick_add.due, None);
+    }
+
+    let quick_add = parse("Call mom today may be late");
+    assert_eq!(quick_add.content, "Call mom may be late");
+    assert_eq!(quick_add.due.as_deref(), Some("today"));
+}
+
+#[test]
+fn parse_reads_next_and_every_before_date_words() {
+    assert_eq!(parse("Review next week").due.as_deref(), Some("next week"));
+    assert_eq!(
+        parse("Water plants every other day").due.as_deref(),
+        Some("every other day")
+    );
+    assert_eq!(
+        parse("Backup every 3 days").due.as_deref(),
+        Some("every 3 days")
+    );
+    assert_eq!(
+        parse("Pay rent every jan 1st").due.as_deref(),
+        Some("every jan 1st")
+    );
+}
+
+#[test]
+fn parse_ignores_bare_markers() {
+    let quick_add = parse("Call # mom @ p5");
+    assert_eq!(quick_add.content, "Call # mom @ p5");
+    assert!(quick_add.labels.is_empty());
+}
+
+#[test]
+fn parse_reads_sections() {
+    let quick_add = parse("Review PR #Work/Backend");
+    assert_eq!(quick_add.project.as_deref(), Some("Work"));
+    assert_eq!(quick_add.section.as_deref(), Some("Backend"));
+
+    let quick_add = parse("Buy milk /Errands");
+    assert_eq!(quick_add.project, None);
+    assert_eq!(quick_add.section.as_deref(), Some("Errands"));
+    assert_eq!(quick_add.content, "Buy milk");
+}
+
+#[test]
+fn resolve_builds_the_task_from_names() {
+    let task = parse("Review PR #work/backend @code p2 every weekday")
+        .resolve(&projects(), &sections(), &labels())
+        .unwrap();
+
+    assert_eq!(
+        task,
+        api::TaskCreate::new(String::from("Review PR"))
+            .project(String::from("2"))
+            .section(String::from("10"))
+            .labels(vec![String::from("Code")])
+            .priority(api::Priority::P2)
+            .due(String::from("every weekday"))
+            .to_owned()
+    );
+}
+
+#[test]
+fn resolve_looks_up_lone_sections_in_the_inbox() {
+    let task = parse("Buy milk /Errands")
+        .resolve(&projects(), &sections(), &labels())
+        .unwrap();
+
+    assert_eq!(task.project_id.as_deref(), Some("1"));
+    assert_eq!(task.section_id.as_deref(), Some("11"));
+}
+
+#[test]
+fn resolve_reports_every_unknown_name() {
+    let unknown = parse("Plan trip #Travel/Flights @booking @code")
+        .resolve(&projects(), &sections(), &labels())
+        .unwrap_err();
+
+    assert_eq!(
+        unknown,
+        vec![
+            Unknown::Project(String::from("Travel")),
+            Unknown::Section {
+                project: Some(String::from("Travel")),
+                name: String::from("Flights")
+            },
+            Unknown::Label(String::from("booking")),
+        ]
+    );
+    assert_eq!(unknown[1].to_string(), "section \"Flights\" in \"Travel\"");
+}
+
+async fn mount_names(server: &MockServer) {
+    let mut inbox = project_json("1", "Inbox");
+    inbox["is_inbox_project"] = json!(true);
+    for (route, body) in [
+        ("/projects", json!([inbox, project_json("2", "Work")])),
+        ("/sections", json!([section_json("10", "2", "Backend")])),
+        ("/labels", json!([label_json("20", "code")])),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn local_add_creates_the_resolved_task() {
+    let server = MockServer::start().await;
+    mount_names(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .and(body_json(json!({
+            "content": "Review PR",
+            "due_string": "every weekday",
+            "priority": 3,
+            "project_id": "2",
+            "section_id": "10",
+            "labels": ["code"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Review PR")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    Cmd::new(&client, &store, Output::Table, Prompt::new(false))
+        .tasks
+        .quick_add(
+            "Review PR #Work/Backend @code p2 every weekday",
+            false,
+            true,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn local_add_without_prompts_fails_on_unknown_names() {
+    let server = MockServer::start().await;
+    mount_names(&server).await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::new(dir.path().to_path_buf(), Duration::from_secs(60));
+    let err = Cmd::new(&client, &store, Output::Table, Prompt::new(false))
+        .tasks
+        .quick_add("Plan trip #Travel", false, true)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::NotFound(_)));
+    assert_eq!(err.to_string(), "project \"Travel\" does not exist");
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[tokio::test]
+async fn quick_add_posts_text_to_the_sync_api() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/sync/v9/quick/add"))
+        .and(header("Authorization", format!("Bearer {TOKEN}").as_str()))
+        .and(body_json(json!({"text": "Pay rent tomorrow #Home p1"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "2995104339",
+            "user_id": "2671355",
+            "project_id": "2203306141",
+            "section_id": null,
+            "parent_id": null,
+            "child_order": 1,
+            "content": "Pay rent",
+            "description": "",
+            "labels": [],
+            "priority": 4,
+            "checked": false,
+            "added_at": "2023-03-01T10:00:00.000000Z",
+            "due": {
+                "date": "2023-03-02",
+                "string": "tomorrow",
+                "is_recurring": false,
+                "timezone": null,
+                "lang": "en"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let item = client(&server)
+        .with_sync_base_url(format!("{}/sync/v9/", server.uri()))
+        .quick_add("Pay rent tomorrow #Home p1")
+        .await
+        .unwrap();
+
+    assert_eq!(item.content, "Pay rent");
+    assert_eq!(item.priority, api::Priority::P1);
+    assert_eq!(item.due.unwrap().string, "tomorrow");
+}
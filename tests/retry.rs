@@ -0,0 +1,152 @@
+mod common;
+
+use serde_json::json;
+use todoist::api;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use common::{client, fast_retry, label_json, task_json};
+
+fn request_id(request: &Request) -> String {
+    request
+        .headers
+        .get(&"X-Request-Id".into())
+        .unwrap()
+        .last()
+        .as_str()
+        .to_owned()
+}
+
+#[tokio::test]
+async fn retries_after_rate_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([label_json("1", "work")])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let labels = client(&server)
+        .with_retry(fast_retry(3))
+        .label_list()
+        .await
+        .unwrap();
+
+    assert_eq!(labels.len(), 1);
+}
+
+#[tokio::test]
+async fn retries_server_errors_until_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .with_retry(fast_retry(2))
+        .label_list()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::Server { .. }));
+}
+
+#[tokio::test]
+async fn does_not_wait_for_long_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/labels"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .with_retry(fast_retry(3))
+        .label_list()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::RateLimited { .. }));
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tasks/1"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .with_retry(fast_retry(3))
+        .view(String::from("1"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, api::Error::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn retried_create_reuses_request_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Buy milk")))
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .with_retry(fast_retry(3))
+        .create(api::TaskCreate::new(String::from("Buy milk")))
+        .await
+        .unwrap();
+
+    let ids = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(request_id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids[0], ids[1]);
+}
+
+#[tokio::test]
+async fn separate_closes_use_different_request_ids() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks/1/close"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    client.close("1").await.unwrap();
+    client.close("1").await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_ne!(request_id(&requests[0]), request_id(&requests[1]));
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use todoist::cmd::{Auth, Prompt};
+use todoist::config::{self, Vars};
+use todoist::secret::{Backend, Error, Secrets};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// A secrets file with few rounds, a file keeps them so the ones created
+/// here are quick to open through the config as well.
+fn secrets_file(dir: &std::path::Path, passphrase: &str) -> Secrets {
+    Secrets::File {
+        path: dir.join("secrets.enc"),
+        passphrase: passphrase.to_string(),
+        rounds: 1,
+    }
+}
+
+#[test]
+fn file_keeps_a_token_per_account() {
+    let dir = tempfile::tempdir().unwrap();
+    let secrets = secrets_file(dir.path(), "passphrase");
+
+    assert_eq!(secrets.get("default").unwrap(), None);
+    secrets.set("default", "token-1").unwrap();
+    secrets.set("work", "token-2").unwrap();
+
+    assert_eq!(secrets.get("default").unwrap().as_deref(), Some("token-1"));
+    assert_eq!(secrets.get("work").unwrap().as_deref(), Some("token-2"));
+    let content = fs::read(dir.path().join("secrets.enc")).unwrap();
+    assert!(!String::from_utf8_lossy(&content).contains("token-1"));
+
+    assert!(secrets.delete("work").unwrap());
+    assert!(!secrets.delete("work").unwrap());
+    assert_eq!(secrets.get("work").unwrap(), None);
+    assert_eq!(secrets.get("default").unwrap().as_deref(), Some("token-1"));
+}
+
+#[test]
+fn file_rejects_wrong_passphrase() {
+    let dir = tempfile::tempdir().unwrap();
+    secrets_file(dir.path(), "right")
+        .set("default", "token")
+        .unwrap();
+
+    let err = secrets_file(dir.path(), "wrong")
+        .get("default")
+        .unwrap_err();
+    assert!(matches!(err, Error::Damaged));
+}
+
+#[test]
+fn config_selects_the_backend() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    fs::write(&path, "secret_backend: file\n").unwrap();
+    let config = config::load(&path, None, &Vars::new()).unwrap();
+
+    assert_eq!(config.secret_backend, Backend::File);
+    assert_eq!(config.account(), "default");
+    assert!(config.secrets(&path, None).is_err());
+    assert!(matches!(
+        config.secrets(&path, Some(String::from("pw"))).unwrap(),
+        Some(Secrets::File { .. })
+    ));
+
+    fs::write(&path, "api_key: token\n").unwrap();
+    let config = config::load(&path, None, &Vars::new()).unwrap();
+    assert!(config.secrets(&path, None).unwrap().is_none());
+}
+
+#[test]
+fn profile_reads_its_own_token_over_the_top_level_one() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    fs::write(
+        &path,
+        "api_key: top-token\nsecret_backend: file\nprofiles:\n  work: {}\n",
+    )
+    .unwrap();
+    secrets_file(dir.path(), "pw")
+        .set("work", "work-token")
+        .unwrap();
+    let vars = Vars::new().with(config::PASSPHRASE_ENV, "pw");
+
+    let config = config::load_with_token(&path, Some("work"), &vars).unwrap();
+    assert_eq!(config.api_key, "work-token");
+
+    let config = config::load_with_token(&path, None, &vars).unwrap();
+    assert_eq!(config.api_key, "top-token");
+}
+
+#[tokio::test]
+async fn login_and_logout_use_the_secrets_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/projects"))
+        .and(header("Authorization", "Bearer new-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.yaml");
+    fs::write(
+        &path,
+        format!(
+            "api_key: old-token\nsecret_backend: file\nbase_url: {}\nprofile: work\nprofiles:\n  work: {{}}\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+    let secrets = secrets_file(dir.path(), "pw");
+    secrets.set("other", "other-token").unwrap();
+    let auth = Auth::new(
+        path.to_owned(),
+        None,
+        Vars::new().with(config::PASSPHRASE_ENV, "pw"),
+        Prompt::new(false),
+    );
+
+    auth.login(&Some(String::from("new-token"))).await.unwrap();
+
+    assert_eq!(secrets.get("work").unwrap().as_deref(), Some("new-token"));
+    // The plain text token of the profile in use is gone, others are kept.
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("api_key: old-token"));
+    // The profile no longer falls back to the top level token.
+    let config = config::load(&path, None, &Vars::new()).unwrap();
+    assert_eq!(config.api_key, "");
+
+    auth.logout().unwrap();
+    assert_eq!(secrets.get("work").unwrap(), None);
+    assert_eq!(
+        secrets.get("other").unwrap().as_deref(),
+        Some("other-token")
+    );
+}
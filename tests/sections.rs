@@ -0,0 +1,146 @@
+mod common;
+
+use serde_json::json;
+use todoist::api;
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{client, section_json, task_json};
+
+#[tokio::test]
+async fn section_list_filters_by_project() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/sections"))
+        .and(query_param("project_id", "2203306141"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([section_json(
+                "1",
+                "2203306141",
+                "Groceries"
+            ),])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let sections = client(&server)
+        .section_list(Some("2203306141"))
+        .await
+        .unwrap();
+
+    assert_eq!(sections[0].name, "Groceries");
+}
+
+#[tokio::test]
+async fn section_list_encodes_project_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/sections"))
+        .and(query_param("project_id", "1&filter=x"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .section_list(Some("1&filter=x"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn section_create_posts_name_and_project() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/sections"))
+        .and(body_json(
+            json!({"name": "Groceries", "project_id": "2203306141"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(section_json(
+            "1",
+            "2203306141",
+            "Groceries",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let section = client(&server)
+        .section_create(api::SectionCreate::new(
+            String::from("Groceries"),
+            String::from("2203306141"),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(section.id, "1");
+}
+
+#[tokio::test]
+async fn section_update_renames() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/sections/1"))
+        .and(body_json(json!({"name": "Shopping"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(section_json(
+            "1",
+            "2203306141",
+            "Shopping",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let section = client(&server)
+        .section_update("1", String::from("Shopping"))
+        .await
+        .unwrap();
+
+    assert_eq!(section.name, "Shopping");
+}
+
+#[tokio::test]
+async fn section_view_and_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/sections/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(section_json(
+            "1",
+            "2203306141",
+            "Groceries",
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/sections/1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    assert_eq!(client.section_view("1").await.unwrap().name, "Groceries");
+    client.section_delete("1").await.unwrap();
+}
+
+#[tokio::test]
+async fn task_create_sends_section() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/tasks"))
+        .and(body_json(json!({"content": "Apples", "section_id": "1"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json("1", "Apples")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server)
+        .create(
+            api::TaskCreate::new(String::from("Apples"))
+                .section(String::from("1"))
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+}
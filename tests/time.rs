@@ -0,0 +1,58 @@
+use chrono::{Local, TimeDelta, Utc};
+use todoist::api::Due;
+use todoist::cmd::time::relative;
+
+fn all_day(days_from_today: i64) -> Due {
+    Due {
+        date: Local::now().date_naive() + TimeDelta::days(days_from_today),
+        ..Default::default()
+    }
+}
+
+fn at(delta: TimeDelta) -> Due {
+    let datetime = Utc::now() + delta;
+    Due {
+        date: datetime.with_timezone(&Local).date_naive(),
+        datetime: Some(datetime),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn all_day_tasks_count_days() {
+    let now = Local::now();
+
+    assert_eq!(relative(&all_day(0), now), "today");
+    assert_eq!(relative(&all_day(1), now), "tomorrow");
+    assert_eq!(relative(&all_day(5), now), "in 5 days");
+    assert_eq!(relative(&all_day(-1), now), "1 day overdue");
+    assert_eq!(relative(&all_day(-3), now), "3 days overdue");
+}
+
+#[test]
+fn timed_tasks_use_the_largest_unit() {
+    let now = Local::now();
+
+    assert_eq!(
+        relative(&at(TimeDelta::minutes(30) + TimeDelta::seconds(5)), now),
+        "in 30m"
+    );
+    assert_eq!(
+        relative(&at(TimeDelta::hours(2) + TimeDelta::minutes(5)), now),
+        "in 2h"
+    );
+    assert_eq!(
+        relative(&at(-TimeDelta::days(3) - TimeDelta::hours(1)), now),
+        "3 days overdue"
+    );
+}
+
+#[test]
+fn all_day_tasks_are_overdue_only_after_their_day() {
+    let now = Local::now();
+
+    assert!(!all_day(0).is_overdue(now));
+    assert!(all_day(-1).is_overdue(now));
+    assert!(at(-TimeDelta::minutes(1)).is_overdue(now));
+    assert!(!at(TimeDelta::minutes(1)).is_overdue(now));
+}
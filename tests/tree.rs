@@ -0,0 +1,47 @@
+use todoist::cmd::tree::flatten;
+
+struct Node {
+    id: &'static str,
+    parent: Option<&'static str>,
+    order: i64,
+}
+
+fn node(id: &'static str, parent: Option<&'static str>, order: i64) -> Node {
+    Node { id, parent, order }
+}
+
+fn render(nodes: &[Node]) -> Vec<(usize, &'static str)> {
+    flatten(nodes, |n| n.id, |n| n.parent, |n| n.order)
+        .into_iter()
+        .map(|(depth, n)| (depth, n.id))
+        .collect()
+}
+
+#[test]
+fn children_follow_their_parent_in_order() {
+    let nodes = [
+        node("work", None, 2),
+        node("backend", Some("work"), 2),
+        node("inbox", None, 1),
+        node("frontend", Some("work"), 1),
+        node("api", Some("backend"), 1),
+    ];
+
+    assert_eq!(
+        render(&nodes),
+        vec![
+            (0, "inbox"),
+            (0, "work"),
+            (1, "frontend"),
+            (1, "backend"),
+            (2, "api"),
+        ]
+    );
+}
+
+#[test]
+fn missing_parent_makes_a_root() {
+    let nodes = [node("orphan", Some("gone"), 1), node("inbox", None, 0)];
+
+    assert_eq!(render(&nodes), vec![(0, "inbox"), (0, "orphan")]);
+}